@@ -0,0 +1,52 @@
+//! Compares per-frame cost of nodes with owned style and shared style.
+//!
+//! Run with `cargo bench --features test_support --bench shared_style`
+
+use std::time::Instant;
+
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{SharedStyle, Tui, TuiBuilderLogic, testing::TuiTestHarness};
+
+const ROWS: usize = 5_000;
+const FRAMES: usize = 50;
+
+fn row_style() -> taffy::Style {
+    taffy::Style {
+        size: taffy::Size {
+            width: length(100.),
+            height: length(20.),
+        },
+        flex_shrink: 0.,
+        ..Default::default()
+    }
+}
+
+/// Print average duration of a frame after the layout has settled
+fn measure(name: &str, mut layout: impl FnMut(&mut Tui)) {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.)).style(taffy::Style {
+        flex_direction: taffy::FlexDirection::Column,
+        ..Default::default()
+    });
+    harness.run(&mut layout);
+
+    let start = Instant::now();
+    harness.run_frames(FRAMES, &mut layout);
+    let frame = start.elapsed() / FRAMES as u32;
+    println!("{name}: {frame:?} per frame ({ROWS} rows)");
+}
+
+fn main() {
+    let style = row_style();
+    measure("owned style", |tui| {
+        for row in 0..ROWS {
+            tui.id(egui::Id::new(row)).style(style.clone()).add_empty();
+        }
+    });
+
+    let shared = SharedStyle::new(row_style());
+    measure("shared style", |tui| {
+        for row in 0..ROWS {
+            tui.id(egui::Id::new(row)).shared_style(&shared).add_empty();
+        }
+    });
+}
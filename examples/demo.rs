@@ -1,6 +1,8 @@
 use eframe::egui::{self, Vec2b};
 use eframe::{App, Frame};
+use egui_taffy::animation::LayoutAnimation;
 use egui_taffy::bg::simple::{TuiBackground, TuiBuilderLogicWithBackground};
+use egui_taffy::inspector::TuiInspector;
 use egui_taffy::{
     TuiBuilderLogic, taffy, tid, tui,
     virtual_tui::{VirtualGridRowHelper, VirtualGridRowHelperParams},
@@ -28,6 +30,10 @@ pub struct State {
     show_background_demo: bool,
     show_holy_grail_demo: bool,
 
+    debug_overlay: bool,
+    show_inspector: bool,
+    inspected_tui: Option<egui::Id>,
+
     grow_variables: Option<GrowVariables>,
     button_params: ButtonParams,
     overflow_demo_params: OverflowParams,
@@ -71,6 +77,10 @@ impl App for MyApp {
 
         custom_background_demo(ctx, state);
 
+        if let Some(inspected_tui) = state.inspected_tui {
+            TuiInspector::new(inspected_tui).show_window(ctx, &mut state.show_inspector);
+        }
+
         holy_grail_demo(ctx, state);
     }
 }
@@ -127,6 +137,16 @@ fn ui_side_panel(ctx: &egui::Context, state: &mut State) {
                         *show = !*show;
                     }
                 }
+
+                tui.separator();
+
+                tui.ui(|ui| {
+                    ui.checkbox(&mut state.debug_overlay, "Layout debug overlay");
+                    ui.checkbox(
+                        &mut state.show_inspector,
+                        "Layout inspector (Flex wrap demo)",
+                    );
+                });
             });
     });
 }
@@ -143,8 +163,12 @@ fn flex_wrap_demo(ctx: &egui::Context, state: &mut State) {
     egui::Window::new("Flex wrap demo")
         .open(&mut state.show_flex_wrap_demo)
         .show(ctx, |ui| {
-            tui(ui, ui.id().with("demo"))
+            let taffy_id = ui.id().with("demo");
+            state.inspected_tui = Some(taffy_id);
+
+            tui(ui, taffy_id)
                 .reserve_available_space() // Reserve full space of window for this layout
+                .debug_overlay(state.debug_overlay)
                 .style(taffy::Style {
                     flex_direction: taffy::FlexDirection::Column,
                     align_items: Some(taffy::AlignItems::Stretch),
@@ -201,9 +225,12 @@ fn flex_wrap_demo(ctx: &egui::Context, state: &mut State) {
                     })
                     .add(|tui| {
                         for word in FLEX_ITEMS {
-                            tui.style(default_style()).add_with_border(|tui| {
-                                tui.label(word);
-                            });
+                            // Animate items when they are moved to another row on window resize
+                            tui.style(default_style())
+                                .animate_layout(LayoutAnimation::default())
+                                .add_with_border(|tui| {
+                                    tui.label(word);
+                                });
                         }
                     });
                 });
@@ -225,6 +252,7 @@ fn flex_grid_demo(ctx: &egui::Context, state: &mut State) {
             // Initialize Tui layout (Taffy ui layout)
             tui(ui, "grid")
                 .reserve_available_space()
+                .debug_overlay(state.debug_overlay)
                 .style(taffy::Style {
                     display: taffy::Display::Grid,
 
@@ -410,6 +438,7 @@ fn flex_demo(ctx: &egui::Context, state: &mut State) {
 
             tui(ui, ui.id().with("demo"))
                 .reserve_available_width()
+                .debug_overlay(state.debug_overlay)
                 .style(taffy::Style {
                     flex_direction: taffy::FlexDirection::Column,
                     min_size: taffy::Size {
@@ -0,0 +1,94 @@
+/// Layout animation settings for nodes that change their position or size between frames.
+///
+/// Node is animated from its previous rectangle to the new layout rectangle (FLIP).
+/// Position and size are animated relative to the parent node, therefore descendants
+/// follow their animated ancestors.
+///
+/// Animations are disabled if egui animations are disabled (`egui::Style::animation_time` is zero),
+/// this can be used to respect reduced motion setting.
+#[derive(Clone, Copy, Debug)]
+pub struct LayoutAnimation {
+    /// Animation duration in seconds
+    pub duration: f32,
+
+    /// Easing function that maps linear animation progress `0..=1` to eased progress.
+    /// See [`egui::emath::easing`] for available functions.
+    pub easing: fn(f32) -> f32,
+}
+
+impl Default for LayoutAnimation {
+    fn default() -> Self {
+        Self {
+            duration: 0.2,
+            easing: egui::emath::easing::cubic_out,
+        }
+    }
+}
+
+impl LayoutAnimation {
+    /// Create layout animation with given duration in seconds
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            ..Default::default()
+        }
+    }
+
+    /// Set easing function
+    pub fn easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// Animation state of a single node.
+///
+/// Rectangles store node location relative to the parent node and node size.
+#[derive(Clone, Copy)]
+pub(crate) struct NodeAnimation {
+    from: egui::Rect,
+    to: egui::Rect,
+    start_time: f64,
+}
+
+impl NodeAnimation {
+    pub(crate) fn new(rect: egui::Rect, time: f64) -> Self {
+        Self {
+            from: rect,
+            to: rect,
+            start_time: time,
+        }
+    }
+
+    /// Update animation target, returns currently shown rectangle and
+    /// if animation is still in progress
+    pub(crate) fn update(
+        &mut self,
+        target: egui::Rect,
+        time: f64,
+        animation: &LayoutAnimation,
+    ) -> (egui::Rect, bool) {
+        if self.to != target {
+            // Continue from the currently shown rectangle
+            self.from = self.current(time, animation).0;
+            self.to = target;
+            self.start_time = time;
+        }
+        self.current(time, animation)
+    }
+
+    fn current(&self, time: f64, animation: &LayoutAnimation) -> (egui::Rect, bool) {
+        let progress = if animation.duration > 0. {
+            ((time - self.start_time) as f32 / animation.duration).clamp(0., 1.)
+        } else {
+            1.
+        };
+
+        if progress >= 1. {
+            return (self.to, false);
+        }
+
+        let t = (animation.easing)(progress);
+        (self.from.lerp_towards(&self.to, t), true)
+    }
+}
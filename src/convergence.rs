@@ -0,0 +1,119 @@
+/// How layout changes are applied when taffy layout is recalculated
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ConvergenceStrategy {
+    /// Discard current pass and immediately run another pass with the new layout
+    /// (See [`egui::Context::request_discard`]). Layout is shown settled in the same frame.
+    #[default]
+    Discard,
+
+    /// Show current pass and apply the new layout in the next frame
+    /// (See [`egui::Context::request_repaint`]). Avoids extra passes.
+    NextFrame,
+}
+
+/// Layout convergence policy, see [`crate::TuiInitializer::convergence`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConvergencePolicy {
+    /// Strategy used when layout changes
+    pub strategy: ConvergenceStrategy,
+
+    /// Maximal count of extra passes in one frame that Tui may request.
+    ///
+    /// egui also limits pass count with `egui::Options::max_passes`.
+    /// When limit is reached, layout is applied in the next frame.
+    pub max_extra_passes: usize,
+}
+
+impl Default for ConvergencePolicy {
+    fn default() -> Self {
+        Self {
+            strategy: ConvergenceStrategy::Discard,
+            max_extra_passes: 2,
+        }
+    }
+}
+
+impl ConvergencePolicy {
+    /// Set strategy used when layout changes
+    pub fn strategy(mut self, strategy: ConvergenceStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set maximal count of extra passes in one frame
+    pub fn max_extra_passes(mut self, max_extra_passes: usize) -> Self {
+        self.max_extra_passes = max_extra_passes;
+        self
+    }
+}
+
+/// What changed in the node
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeChangeKind {
+    /// Node was added to the layout
+    Added,
+    /// Node style changed
+    Style,
+    /// Leaf node sizing context ([`crate::Context`]) changed
+    Context,
+}
+
+/// Node change that made layout dirty
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeChange {
+    /// egui pass index in the frame (See [`egui::Context::current_pass_index`])
+    pub pass: usize,
+    /// Node id
+    pub id: egui::Id,
+    /// What changed
+    pub kind: NodeChangeKind,
+}
+
+/// Per-frame layout convergence report, see [`crate::TaffyState::convergence_report`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConvergenceReport {
+    /// egui frame number (See [`egui::Context::cumulative_frame_nr`])
+    pub frame_nr: u64,
+
+    /// Count of passes in which the layout was shown during the frame
+    pub passes: usize,
+
+    /// Count of layout recalculations during the frame, including measure phases
+    /// (See [`crate::TuiInitializer::show_two_phase`])
+    pub recalculations: usize,
+
+    /// Layout was not recalculated in the last pass (Shown layout is settled)
+    pub converged: bool,
+
+    /// Another pass was needed, but it was not requested because of the convergence policy
+    /// or egui pass limit
+    pub deferred: bool,
+
+    /// Node changes that made layout dirty
+    pub changes: Vec<NodeChange>,
+}
+
+impl ConvergenceReport {
+    /// Start report for the new pass. Report is reset if pass belongs to a new frame.
+    ///
+    /// Measure phase (See [`crate::TuiInitializer::show_two_phase`]) is not counted as a pass,
+    /// its changes and recalculations are accumulated with the following pass.
+    pub(crate) fn begin_pass(&mut self, frame_nr: u64, measure_phase: bool) {
+        if self.frame_nr != frame_nr {
+            *self = Self {
+                frame_nr,
+                ..Default::default()
+            };
+        }
+        if measure_phase {
+            return;
+        }
+        self.passes += 1;
+        self.converged = true;
+        self.deferred = false;
+    }
+
+    pub(crate) fn record(&mut self, pass: usize, id: egui::Id, kind: NodeChangeKind) {
+        self.changes.push(NodeChange { pass, id, kind });
+    }
+}
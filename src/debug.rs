@@ -0,0 +1,149 @@
+use egui::Color32;
+
+use crate::{Context, TaffyContainerUi};
+
+/// Translucent colors used to paint node box model (Same as in browser developer tools)
+const MARGIN_COLOR: Color32 = Color32::from_rgba_premultiplied(93, 67, 40, 96);
+const BORDER_COLOR: Color32 = Color32::from_rgba_premultiplied(96, 86, 58, 96);
+const PADDING_COLOR: Color32 = Color32::from_rgba_premultiplied(55, 74, 47, 96);
+const CONTENT_COLOR: Color32 = Color32::from_rgba_premultiplied(42, 63, 83, 96);
+
+/// Information about node that is shown in debug overlay tooltip
+pub(crate) struct DebugNodeInfo<'a> {
+    pub id: egui::Id,
+    pub tui_id_kind: &'static str,
+    pub display: taffy::Display,
+    pub context: Option<&'a Context>,
+}
+
+/// Paint box model of the node: margin, border, padding and content rectangles.
+///
+/// Returns true if pointer hovers the node.
+pub(crate) fn paint_box_model(ui: &egui::Ui, container: &TaffyContainerUi) -> bool {
+    let margin = container.full_container_with_margin();
+    let border = container.full_container();
+    let padding = container.full_container_without_border();
+    let content = container.full_container_without_border_and_padding();
+
+    if [margin, border, padding, content]
+        .iter()
+        .any(|rect| rect.any_nan())
+    {
+        return false;
+    }
+
+    let painter = ui.painter();
+    paint_ring(painter, margin, border, MARGIN_COLOR);
+    paint_ring(painter, border, padding, BORDER_COLOR);
+    paint_ring(painter, padding, content, PADDING_COLOR);
+
+    painter.rect_stroke(
+        content,
+        0.,
+        egui::Stroke::new(1., CONTENT_COLOR),
+        egui::StrokeKind::Inside,
+    );
+
+    ui.rect_contains_pointer(border)
+}
+
+/// Fill content rectangle of hovered node
+pub(crate) fn paint_hovered_content(ui: &egui::Ui, container: &TaffyContainerUi) {
+    let content = container.full_container_without_border_and_padding();
+    ui.painter().rect_filled(content, 0., CONTENT_COLOR);
+}
+
+/// Fill area between outer and inner rectangle
+fn paint_ring(painter: &egui::Painter, outer: egui::Rect, inner: egui::Rect, color: Color32) {
+    let inner = inner.intersect(outer);
+    if inner == outer {
+        return;
+    }
+
+    let sides = [
+        egui::Rect::from_x_y_ranges(outer.x_range(), outer.top()..=inner.top()),
+        egui::Rect::from_x_y_ranges(outer.x_range(), inner.bottom()..=outer.bottom()),
+        egui::Rect::from_x_y_ranges(outer.left()..=inner.left(), inner.y_range()),
+        egui::Rect::from_x_y_ranges(inner.right()..=outer.right(), inner.y_range()),
+    ];
+    for side in sides {
+        if side.is_positive() {
+            painter.rect_filled(side, 0., color);
+        }
+    }
+}
+
+/// Show tooltip with information about hovered node
+pub(crate) fn show_node_tooltip(
+    ui: &egui::Ui,
+    container: &TaffyContainerUi,
+    info: DebugNodeInfo<'_>,
+) {
+    let DebugNodeInfo {
+        id,
+        tui_id_kind,
+        display,
+        context,
+    } = info;
+
+    egui::Tooltip::always_open(
+        ui.ctx().clone(),
+        ui.layer_id(),
+        egui::Id::new("egui_taffy_debug_overlay"),
+        egui::PopupAnchor::Pointer,
+    )
+    .gap(12.)
+    .show(|ui| {
+        let layout = container.layout();
+        egui::Grid::new("egui_taffy_debug_overlay_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Id");
+                ui.monospace(format!("{id:?}"));
+                ui.end_row();
+
+                ui.label("TuiId");
+                ui.monospace(tui_id_kind);
+                ui.end_row();
+
+                ui.label("Display");
+                ui.monospace(format!("{display:?}"));
+                ui.end_row();
+
+                ui.label("Size");
+                ui.monospace(format!("{} x {}", layout.size.width, layout.size.height));
+                ui.end_row();
+
+                ui.label("Content size");
+                ui.monospace(format!(
+                    "{} x {}",
+                    layout.content_size.width, layout.content_size.height
+                ));
+                ui.end_row();
+
+                if let Some(context) = context {
+                    ui.label("Measured min size");
+                    ui.monospace(format!("{:?}", context.min_size()));
+                    ui.end_row();
+
+                    ui.label("Measured max size");
+                    ui.monospace(format!("{:?}", context.max_size()));
+                    ui.end_row();
+
+                    ui.label("Infinite");
+                    ui.monospace(format!("{:?}", context.infinite()));
+                    ui.end_row();
+
+                    ui.label("Measured by");
+                    ui.monospace(if context.has_custom_measure() {
+                        "TuiMeasure"
+                    } else if context.text_layout().is_some() {
+                        "Text layout"
+                    } else {
+                        "Size constraints"
+                    });
+                    ui.end_row();
+                }
+            });
+    });
+}
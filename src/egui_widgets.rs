@@ -1,4 +1,8 @@
-use crate::{TuiBuilderLogic, TuiContainerResponse};
+use std::sync::Arc;
+
+use egui::text::LayoutJob;
+
+use crate::{TuiBuilderLogic, TuiContainerResponse, layer_next_shape_idx};
 
 use super::{TuiBuilder, TuiWidget};
 
@@ -34,23 +38,127 @@ impl_widget!(
 impl TuiWidget for egui::Label {
     type Response = egui::Response;
     fn taffy_ui(self, tuib: TuiBuilder) -> Self::Response {
-        // Egui intrinsic size doesn't take into account text wrapping
-        let wrap_mode = true; // This shouldn't cause problems even in non wrap mode
-        // let wrap_mode = tuib.params.wrap_mode == Some(egui::TextWrapMode::Wrap);
+        tuib.ui_manual(|ui, _container| {
+            // Label text is not accessible, therefore label text layout is taken from the
+            // galley it was laid out with
+            let text_hash = egui::util::hash(self.text());
+            let (response, text_layout) = if ui.is_visible() {
+                // Label is added as is to preserve all label options
+                let first_shape = layer_next_shape_idx(ui);
+                let response = ui.add(self);
+                let text_layout = painted_text_layout(ui, first_shape, text_hash);
+                (response, text_layout)
+            } else {
+                // Invisible label is only laid out (the same as in `egui::Label::ui`)
+                let (_, galley, response) = self.layout_in_ui(ui);
+                response.widget_info(|| {
+                    egui::WidgetInfo::labeled(
+                        egui::WidgetType::Label,
+                        ui.is_enabled(),
+                        galley.text(),
+                    )
+                });
+                let text_layout = galley.job.clone();
+                store_label_text_layout(ui, text_hash, &text_layout);
+                (response, Some(text_layout))
+            };
 
-        tuib.ui_add_manual(
-            |ui| ui.add(self),
-            |mut response, _ui| {
-                if wrap_mode {
-                    // Fix intrinsic size of text
-                    if let Some(intrinsic_size) = response.intrinsic_size.as_mut() {
-                        intrinsic_size.y = intrinsic_size.y.max(response.min_size.y);
-                    }
+            let mut response = TuiContainerResponse {
+                min_size: response.rect.size(),
+                intrinsic_size: response.intrinsic_size,
+                max_size: response.rect.size(),
+                infinite: egui::Vec2b::FALSE,
+                text_layout,
+                measure: None,
+                inner: response,
+            };
+            fix_label_intrinsic_size(&mut response);
+            response
+        })
+    }
+}
+
+fn label_text_layout_id(ui: &egui::Ui) -> egui::Id {
+    ui.id().with("egui_taffy_label_text_layout")
+}
+
+fn store_label_text_layout(ui: &egui::Ui, text_hash: u64, text_layout: &Arc<LayoutJob>) {
+    ui.data_mut(|data| {
+        data.insert_temp(label_text_layout_id(ui), (text_hash, text_layout.clone()));
+    });
+}
+
+/// Text layout of the label galley painted starting from the given shape index.
+///
+/// Label outside of the clip rectangle is not painted, in that case text layout stored
+/// during the last frame in which label with the same text was laid out is used.
+fn painted_text_layout(
+    ui: &egui::Ui,
+    first_shape: usize,
+    text_hash: u64,
+) -> Option<Arc<LayoutJob>> {
+    let painted = ui.ctx().graphics(|graphics| {
+        graphics.get(ui.layer_id()).and_then(|paint_list| {
+            paint_list.all_entries().skip(first_shape).find_map(
+                |clipped_shape| match &clipped_shape.shape {
+                    egui::Shape::Text(text_shape) => Some(text_shape.galley.job.clone()),
+                    _ => None,
+                },
+            )
+        })
+    });
+
+    match painted {
+        Some(text_layout) => {
+            store_label_text_layout(ui, text_hash, &text_layout);
+            Some(text_layout)
+        }
+        None => ui
+            .data(|data| data.get_temp::<(u64, Arc<LayoutJob>)>(label_text_layout_id(ui)))
+            .filter(|(hash, _)| *hash == text_hash)
+            .map(|(_, text_layout)| text_layout),
+    }
+}
+
+/// Add label as leaf node. Label text is measured at exact width during layout calculation.
+pub(crate) fn text_label(tuib: TuiBuilder, text: egui::WidgetText) -> egui::Response {
+    let label_text = text.clone();
+
+    tuib.ui_add_manual(
+        |ui| ui.add(egui::Label::new(text)),
+        |mut response, ui| {
+            fix_label_intrinsic_size(&mut response);
+
+            let mut text_layout = Arc::unwrap_or_clone(label_text.into_layout_job(
+                ui.style(),
+                egui::FontSelection::Default,
+                ui.text_valign(),
+            ));
+            match ui.wrap_mode() {
+                egui::TextWrapMode::Extend => {
+                    text_layout.wrap.max_width = f32::INFINITY;
+                }
+                egui::TextWrapMode::Wrap => {
+                    text_layout.wrap.max_width = 0.;
                 }
+                egui::TextWrapMode::Truncate => {
+                    text_layout.wrap.max_width = 0.;
+                    text_layout.wrap.max_rows = 1;
+                    text_layout.wrap.break_anywhere = true;
+                }
+            }
+            response.text_layout = Some(Arc::new(text_layout));
 
-                response
-            },
-        )
+            response
+        },
+    )
+}
+
+fn fix_label_intrinsic_size(response: &mut TuiContainerResponse<egui::Response>) {
+    // Egui intrinsic size doesn't take into account text wrapping
+    // This shouldn't cause problems even in non wrap mode
+    if let Some(intrinsic_size) = response.intrinsic_size.as_mut() {
+        intrinsic_size.y = intrinsic_size.y.max(response.min_size.y);
     }
 }
 
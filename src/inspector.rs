@@ -0,0 +1,1053 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use taffy::prelude::*;
+use taffy::{
+    MaxTrackSizingFunction, MinTrackSizingFunction, NonRepeatedTrackSizingFunction, Overflow,
+    Point, TextAlign, TrackSizingFunction,
+};
+
+use crate::TaffyState;
+
+/// Live layout inspector.
+///
+/// Shows taffy node tree of egui_taffy instance identified by [`crate::Tui::main_taffy_id`].
+/// Selected node is highlighted in the application and its style can be edited.
+/// Style edits are applied as runtime overrides on top of the style provided by the application,
+/// see [`TaffyState::set_style_override`].
+///
+/// ```ignore
+/// TuiInspector::new(taffy_id).show_window(ctx, &mut open);
+/// ```
+pub struct TuiInspector {
+    taffy_id: egui::Id,
+}
+
+impl TuiInspector {
+    /// Create inspector for egui_taffy instance with given id
+    pub fn new(taffy_id: impl Into<egui::Id>) -> Self {
+        Self {
+            taffy_id: taffy_id.into(),
+        }
+    }
+
+    /// Show inspector in a separate window
+    pub fn show_window(self, ctx: &egui::Context, open: &mut bool) {
+        egui::Window::new("Layout inspector")
+            .id(self.taffy_id.with("inspector_window"))
+            .open(open)
+            .default_width(400.)
+            .show(ctx, |ui| {
+                self.ui(ui);
+            });
+    }
+
+    /// Show inspector in given ui (For example, in a side panel)
+    pub fn ui(self, ui: &mut egui::Ui) {
+        let taffy_id = self.taffy_id;
+        let ctx = ui.ctx().clone();
+        let shown = TaffyState::with(&ctx, taffy_id, |state| {
+            let mut selected = state.highlighted_node();
+
+            egui::ScrollArea::vertical()
+                .id_salt(taffy_id.with("inspector_tree"))
+                .max_height(ui.available_height() / 2.)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    node_tree_ui(ui, state, &mut selected);
+                });
+
+            if selected != state.highlighted_node() {
+                state.set_highlighted_node(selected);
+                ui.ctx().request_repaint();
+            }
+
+            ui.separator();
+
+            let Some(selected) = selected else {
+                ui.label("Select node to inspect its style");
+                return;
+            };
+
+            egui::ScrollArea::vertical()
+                .id_salt(taffy_id.with("inspector_style"))
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    node_style_ui(ui, state, selected);
+                });
+        });
+
+        if shown.is_none() {
+            ui.label("Layout state is not available");
+        }
+    }
+}
+
+/// Show taffy node tree starting from root node
+fn node_tree_ui(ui: &mut egui::Ui, state: &TaffyState, selected: &mut Option<egui::Id>) {
+    let Some(root) = state.root_node() else {
+        ui.label("Layout is empty");
+        return;
+    };
+
+    let node_ids = state.node_egui_ids();
+
+    node_ui(ui, state, node_ids, root, selected);
+}
+
+fn node_ui(
+    ui: &mut egui::Ui,
+    state: &TaffyState,
+    node_ids: &HashMap<NodeId, egui::Id>,
+    node_id: NodeId,
+    selected: &mut Option<egui::Id>,
+) {
+    let Some(&id) = node_ids.get(&node_id) else {
+        return;
+    };
+    let taffy_tree = state.taffy_tree();
+    let display = taffy_tree.style(node_id).unwrap().display;
+    let size = taffy_tree.layout(node_id).unwrap().size;
+
+    let mut text = format!("{display:?} {:?} ({} x {})", id, size.width, size.height);
+    if state.style_override(id).is_some() {
+        text.push_str(" *");
+    }
+
+    let header = |ui: &mut egui::Ui| {
+        if ui.selectable_label(*selected == Some(id), text).clicked() {
+            *selected = if *selected == Some(id) {
+                None
+            } else {
+                Some(id)
+            };
+        }
+    };
+
+    if taffy_tree.child_count(node_id) == 0 {
+        ui.horizontal(|ui| {
+            // Align leaf nodes with collapsible node headers
+            ui.add_space(ui.spacing().icon_width + ui.spacing().icon_spacing);
+            header(ui);
+        });
+        return;
+    }
+
+    egui::collapsing_header::CollapsingState::load_with_default_open(
+        ui.ctx(),
+        ui.id().with(id),
+        true,
+    )
+    .show_header(ui, header)
+    .body(|ui| {
+        for child in taffy_tree.child_ids(node_id) {
+            node_ui(ui, state, node_ids, child, selected);
+        }
+    });
+}
+
+/// Show editable style of the selected node
+fn node_style_ui(ui: &mut egui::Ui, state: &mut TaffyState, id: egui::Id) {
+    let Some(node_data) = state.items().get(&id) else {
+        ui.label("Selected node does not exist anymore");
+        return;
+    };
+
+    let has_override = state.style_override(id).is_some();
+    let mut style = match state.style_override(id) {
+        Some(style_override) => style_override.clone(),
+        None => {
+            let mut style = state.taffy_tree().style(node_data.node_id).unwrap().clone();
+            if let Some(inset) = node_data.sticky_inset {
+                // Sticky inset is not stored in taffy style
+                style.inset = inset;
+            }
+            style
+        }
+    };
+
+    ui.horizontal(|ui| {
+        if ui.button("Copy as Rust code").clicked() {
+            ui.ctx().copy_text(style_to_rust_code(&style));
+        }
+        if ui
+            .add_enabled(has_override, egui::Button::new("Reset overrides"))
+            .clicked()
+        {
+            state.remove_style_override(id);
+            ui.ctx().request_repaint();
+        }
+    });
+
+    let changed = egui::Grid::new(id.with("inspector_style_grid"))
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| style_editor_ui(ui, &mut style))
+        .inner;
+
+    if changed {
+        state.set_style_override(id, style);
+        ui.ctx().request_repaint();
+    }
+}
+
+/// Helper to show one row in style editor grid
+fn row(ui: &mut egui::Ui, label: &str, edit: impl FnOnce(&mut egui::Ui) -> bool) -> bool {
+    ui.label(label);
+    let changed = ui.horizontal_wrapped(edit).inner;
+    ui.end_row();
+    changed
+}
+
+/// Show editors for all style fields, returns true if style was changed
+fn style_editor_ui(ui: &mut egui::Ui, style: &mut Style) -> bool {
+    let mut changed = false;
+
+    changed |= row(ui, "display", |ui| {
+        enum_combo(
+            ui,
+            "display",
+            &mut style.display,
+            &[Display::Block, Display::Flex, Display::Grid, Display::None],
+        )
+    });
+    changed |= row(ui, "box_sizing", |ui| {
+        enum_combo(
+            ui,
+            "box_sizing",
+            &mut style.box_sizing,
+            &[BoxSizing::BorderBox, BoxSizing::ContentBox],
+        )
+    });
+    changed |= row(ui, "position", |ui| {
+        enum_combo(
+            ui,
+            "position",
+            &mut style.position,
+            &[Position::Relative, Position::Absolute],
+        )
+    });
+    changed |= row(ui, "overflow", |ui| {
+        let overflow = [
+            Overflow::Visible,
+            Overflow::Clip,
+            Overflow::Hidden,
+            Overflow::Scroll,
+        ];
+        edit_point(ui, "overflow", &mut style.overflow, |ui, salt, value| {
+            enum_combo(ui, salt, value, &overflow)
+        })
+    });
+    changed |= row(ui, "scrollbar_width", |ui| {
+        edit_f32(ui, &mut style.scrollbar_width)
+    });
+    changed |= row(ui, "inset", |ui| {
+        edit_rect(ui, "inset", &mut style.inset, edit_length_percentage_auto)
+    });
+    changed |= row(ui, "size", |ui| {
+        edit_size(ui, "size", &mut style.size, edit_dimension)
+    });
+    changed |= row(ui, "min_size", |ui| {
+        edit_size(ui, "min_size", &mut style.min_size, edit_dimension)
+    });
+    changed |= row(ui, "max_size", |ui| {
+        edit_size(ui, "max_size", &mut style.max_size, edit_dimension)
+    });
+    changed |= row(ui, "aspect_ratio", |ui| {
+        edit_option_f32(ui, &mut style.aspect_ratio, 1.)
+    });
+    changed |= row(ui, "margin", |ui| {
+        edit_rect(ui, "margin", &mut style.margin, edit_length_percentage_auto)
+    });
+    changed |= row(ui, "padding", |ui| {
+        edit_rect(ui, "padding", &mut style.padding, edit_length_percentage)
+    });
+    changed |= row(ui, "border", |ui| {
+        edit_rect(ui, "border", &mut style.border, edit_length_percentage)
+    });
+
+    let align_items = [
+        AlignItems::Start,
+        AlignItems::End,
+        AlignItems::FlexStart,
+        AlignItems::FlexEnd,
+        AlignItems::Center,
+        AlignItems::Baseline,
+        AlignItems::Stretch,
+    ];
+    let align_content = [
+        AlignContent::Start,
+        AlignContent::End,
+        AlignContent::FlexStart,
+        AlignContent::FlexEnd,
+        AlignContent::Center,
+        AlignContent::Stretch,
+        AlignContent::SpaceBetween,
+        AlignContent::SpaceEvenly,
+        AlignContent::SpaceAround,
+    ];
+    changed |= row(ui, "align_items", |ui| {
+        option_enum_combo(ui, "align_items", &mut style.align_items, &align_items)
+    });
+    changed |= row(ui, "align_self", |ui| {
+        option_enum_combo(ui, "align_self", &mut style.align_self, &align_items)
+    });
+    changed |= row(ui, "justify_items", |ui| {
+        option_enum_combo(ui, "justify_items", &mut style.justify_items, &align_items)
+    });
+    changed |= row(ui, "justify_self", |ui| {
+        option_enum_combo(ui, "justify_self", &mut style.justify_self, &align_items)
+    });
+    changed |= row(ui, "align_content", |ui| {
+        option_enum_combo(
+            ui,
+            "align_content",
+            &mut style.align_content,
+            &align_content,
+        )
+    });
+    changed |= row(ui, "justify_content", |ui| {
+        option_enum_combo(
+            ui,
+            "justify_content",
+            &mut style.justify_content,
+            &align_content,
+        )
+    });
+    changed |= row(ui, "gap", |ui| {
+        edit_size(ui, "gap", &mut style.gap, edit_length_percentage)
+    });
+    changed |= row(ui, "text_align", |ui| {
+        enum_combo(
+            ui,
+            "text_align",
+            &mut style.text_align,
+            &[
+                TextAlign::Auto,
+                TextAlign::LegacyLeft,
+                TextAlign::LegacyRight,
+                TextAlign::LegacyCenter,
+            ],
+        )
+    });
+
+    changed |= row(ui, "flex_direction", |ui| {
+        enum_combo(
+            ui,
+            "flex_direction",
+            &mut style.flex_direction,
+            &[
+                FlexDirection::Row,
+                FlexDirection::Column,
+                FlexDirection::RowReverse,
+                FlexDirection::ColumnReverse,
+            ],
+        )
+    });
+    changed |= row(ui, "flex_wrap", |ui| {
+        enum_combo(
+            ui,
+            "flex_wrap",
+            &mut style.flex_wrap,
+            &[FlexWrap::NoWrap, FlexWrap::Wrap, FlexWrap::WrapReverse],
+        )
+    });
+    changed |= row(ui, "flex_basis", |ui| {
+        edit_dimension(ui, "flex_basis", &mut style.flex_basis)
+    });
+    changed |= row(ui, "flex_grow", |ui| edit_f32(ui, &mut style.flex_grow));
+    changed |= row(ui, "flex_shrink", |ui| edit_f32(ui, &mut style.flex_shrink));
+
+    changed |= row(ui, "grid_template_rows", |ui| {
+        edit_tracks(ui, "grid_template_rows", &mut style.grid_template_rows)
+    });
+    changed |= row(ui, "grid_template_columns", |ui| {
+        edit_tracks(
+            ui,
+            "grid_template_columns",
+            &mut style.grid_template_columns,
+        )
+    });
+    changed |= row(ui, "grid_auto_rows", |ui| {
+        edit_auto_tracks(ui, "grid_auto_rows", &mut style.grid_auto_rows)
+    });
+    changed |= row(ui, "grid_auto_columns", |ui| {
+        edit_auto_tracks(ui, "grid_auto_columns", &mut style.grid_auto_columns)
+    });
+    changed |= row(ui, "grid_auto_flow", |ui| {
+        enum_combo(
+            ui,
+            "grid_auto_flow",
+            &mut style.grid_auto_flow,
+            &[
+                GridAutoFlow::Row,
+                GridAutoFlow::Column,
+                GridAutoFlow::RowDense,
+                GridAutoFlow::ColumnDense,
+            ],
+        )
+    });
+    changed |= row(ui, "grid_row", |ui| {
+        edit_placement(ui, ("grid_row", "start"), &mut style.grid_row.start)
+            | edit_placement(ui, ("grid_row", "end"), &mut style.grid_row.end)
+    });
+    changed |= row(ui, "grid_column", |ui| {
+        edit_placement(ui, ("grid_column", "start"), &mut style.grid_column.start)
+            | edit_placement(ui, ("grid_column", "end"), &mut style.grid_column.end)
+    });
+
+    changed
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+fn enum_combo<T: PartialEq + Copy + Debug>(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    value: &mut T,
+    options: &[T],
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(format!("{value:?}"))
+        .show_ui(ui, |ui| {
+            for option in options {
+                changed |= ui
+                    .selectable_value(value, *option, format!("{option:?}"))
+                    .changed();
+            }
+        });
+    changed
+}
+
+fn option_enum_combo<T: PartialEq + Copy + Debug>(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    value: &mut Option<T>,
+    options: &[T],
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(match value {
+            Some(value) => format!("{value:?}"),
+            None => "None".to_owned(),
+        })
+        .show_ui(ui, |ui| {
+            changed |= ui.selectable_value(value, None, "None").changed();
+            for option in options {
+                changed |= ui
+                    .selectable_value(value, Some(*option), format!("{option:?}"))
+                    .changed();
+            }
+        });
+    changed
+}
+
+fn edit_f32(ui: &mut egui::Ui, value: &mut f32) -> bool {
+    ui.add(egui::DragValue::new(value).speed(0.1)).changed()
+}
+
+fn edit_option_f32(ui: &mut egui::Ui, value: &mut Option<f32>, default: f32) -> bool {
+    let mut enabled = value.is_some();
+    let mut changed = ui.checkbox(&mut enabled, "").changed();
+    if changed {
+        *value = enabled.then_some(default);
+    }
+    if let Some(value) = value {
+        changed |= edit_f32(ui, value);
+    }
+    changed
+}
+
+/// Edit value that consists of unit and optional number
+fn edit_unit_value(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    units: &[(&str, bool)],
+    unit: &mut usize,
+    number: &mut f32,
+) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id_salt)
+        .width(60.)
+        .selected_text(units[*unit].0)
+        .show_ui(ui, |ui| {
+            for (idx, (name, _)) in units.iter().enumerate() {
+                changed |= ui.selectable_value(unit, idx, *name).changed();
+            }
+        });
+    if units[*unit].1 {
+        changed |= ui.add(egui::DragValue::new(number).speed(0.1)).changed();
+    }
+    changed
+}
+
+const LENGTH: (&str, bool) = ("px", true);
+const PERCENT: (&str, bool) = ("%", true);
+const AUTO: (&str, bool) = ("auto", false);
+
+fn edit_dimension(ui: &mut egui::Ui, id_salt: impl Hash, value: &mut Dimension) -> bool {
+    let (mut unit, mut number) = match *value {
+        Dimension::Length(number) => (0, number),
+        Dimension::Percent(number) => (1, number),
+        Dimension::Auto => (2, 0.),
+    };
+    let changed = edit_unit_value(
+        ui,
+        id_salt,
+        &[LENGTH, PERCENT, AUTO],
+        &mut unit,
+        &mut number,
+    );
+    if changed {
+        *value = match unit {
+            0 => Dimension::Length(number),
+            1 => Dimension::Percent(number),
+            _ => Dimension::Auto,
+        };
+    }
+    changed
+}
+
+fn edit_length_percentage(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    value: &mut LengthPercentage,
+) -> bool {
+    let (mut unit, mut number) = match *value {
+        LengthPercentage::Length(number) => (0, number),
+        LengthPercentage::Percent(number) => (1, number),
+    };
+    let changed = edit_unit_value(ui, id_salt, &[LENGTH, PERCENT], &mut unit, &mut number);
+    if changed {
+        *value = match unit {
+            0 => LengthPercentage::Length(number),
+            _ => LengthPercentage::Percent(number),
+        };
+    }
+    changed
+}
+
+fn edit_length_percentage_auto(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    value: &mut LengthPercentageAuto,
+) -> bool {
+    let (mut unit, mut number) = match *value {
+        LengthPercentageAuto::Length(number) => (0, number),
+        LengthPercentageAuto::Percent(number) => (1, number),
+        LengthPercentageAuto::Auto => (2, 0.),
+    };
+    let changed = edit_unit_value(
+        ui,
+        id_salt,
+        &[LENGTH, PERCENT, AUTO],
+        &mut unit,
+        &mut number,
+    );
+    if changed {
+        *value = match unit {
+            0 => LengthPercentageAuto::Length(number),
+            1 => LengthPercentageAuto::Percent(number),
+            _ => LengthPercentageAuto::Auto,
+        };
+    }
+    changed
+}
+
+fn edit_rect<T>(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    rect: &mut Rect<T>,
+    edit: impl Fn(&mut egui::Ui, egui::Id, &mut T) -> bool,
+) -> bool {
+    ui.vertical(|ui| {
+        let mut changed = false;
+        for (side, value) in [
+            ("left", &mut rect.left),
+            ("right", &mut rect.right),
+            ("top", &mut rect.top),
+            ("bottom", &mut rect.bottom),
+        ] {
+            changed |= ui
+                .horizontal(|ui| {
+                    ui.label(side);
+                    edit(ui, egui::Id::new((id_salt, side)), value)
+                })
+                .inner;
+        }
+        changed
+    })
+    .inner
+}
+
+fn edit_size<T>(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    size: &mut Size<T>,
+    edit: impl Fn(&mut egui::Ui, egui::Id, &mut T) -> bool,
+) -> bool {
+    ui.vertical(|ui| {
+        let mut changed = false;
+        for (dimension, value) in [("width", &mut size.width), ("height", &mut size.height)] {
+            changed |= ui
+                .horizontal(|ui| {
+                    ui.label(dimension);
+                    edit(ui, egui::Id::new((id_salt, dimension)), value)
+                })
+                .inner;
+        }
+        changed
+    })
+    .inner
+}
+
+fn edit_point<T>(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    point: &mut Point<T>,
+    edit: impl Fn(&mut egui::Ui, egui::Id, &mut T) -> bool,
+) -> bool {
+    let mut changed = false;
+    for (axis, value) in [("x", &mut point.x), ("y", &mut point.y)] {
+        ui.label(axis);
+        changed |= edit(ui, egui::Id::new((id_salt, axis)), value);
+    }
+    changed
+}
+
+fn edit_placement(ui: &mut egui::Ui, id_salt: impl Hash, value: &mut GridPlacement) -> bool {
+    let (mut unit, mut number) = match *value {
+        GridPlacement::Auto => (0, 0.),
+        GridPlacement::Line(line) => (1, line.as_i16() as f32),
+        GridPlacement::Span(span) => (2, span as f32),
+    };
+    let changed = edit_unit_value(
+        ui,
+        id_salt,
+        &[("auto", false), ("line", true), ("span", true)],
+        &mut unit,
+        &mut number,
+    );
+    if changed {
+        *value = match unit {
+            0 => GridPlacement::Auto,
+            1 => line(number.round() as i16),
+            _ => GridPlacement::Span(number.round().max(1.) as u16),
+        };
+    }
+    changed
+}
+
+fn edit_min_track(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    value: &mut MinTrackSizingFunction,
+) -> bool {
+    let (mut unit, mut number) = match *value {
+        MinTrackSizingFunction::Fixed(LengthPercentage::Length(number)) => (0, number),
+        MinTrackSizingFunction::Fixed(LengthPercentage::Percent(number)) => (1, number),
+        MinTrackSizingFunction::MinContent => (2, 0.),
+        MinTrackSizingFunction::MaxContent => (3, 0.),
+        MinTrackSizingFunction::Auto => (4, 0.),
+    };
+    let changed = edit_unit_value(
+        ui,
+        id_salt,
+        &[
+            LENGTH,
+            PERCENT,
+            ("min-content", false),
+            ("max-content", false),
+            AUTO,
+        ],
+        &mut unit,
+        &mut number,
+    );
+    if changed {
+        *value = match unit {
+            0 => MinTrackSizingFunction::Fixed(LengthPercentage::Length(number)),
+            1 => MinTrackSizingFunction::Fixed(LengthPercentage::Percent(number)),
+            2 => MinTrackSizingFunction::MinContent,
+            3 => MinTrackSizingFunction::MaxContent,
+            _ => MinTrackSizingFunction::Auto,
+        };
+    }
+    changed
+}
+
+fn edit_max_track(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    value: &mut MaxTrackSizingFunction,
+) -> bool {
+    let (mut unit, mut number) = match *value {
+        MaxTrackSizingFunction::Fixed(LengthPercentage::Length(number)) => (0, number),
+        MaxTrackSizingFunction::Fixed(LengthPercentage::Percent(number)) => (1, number),
+        MaxTrackSizingFunction::MinContent => (2, 0.),
+        MaxTrackSizingFunction::MaxContent => (3, 0.),
+        MaxTrackSizingFunction::FitContent(LengthPercentage::Length(number)) => (4, number),
+        MaxTrackSizingFunction::FitContent(LengthPercentage::Percent(number)) => (5, number),
+        MaxTrackSizingFunction::Auto => (6, 0.),
+        MaxTrackSizingFunction::Fraction(number) => (7, number),
+    };
+    let changed = edit_unit_value(
+        ui,
+        id_salt,
+        &[
+            LENGTH,
+            PERCENT,
+            ("min-content", false),
+            ("max-content", false),
+            ("fit-content px", true),
+            ("fit-content %", true),
+            AUTO,
+            ("fr", true),
+        ],
+        &mut unit,
+        &mut number,
+    );
+    if changed {
+        *value = match unit {
+            0 => MaxTrackSizingFunction::Fixed(LengthPercentage::Length(number)),
+            1 => MaxTrackSizingFunction::Fixed(LengthPercentage::Percent(number)),
+            2 => MaxTrackSizingFunction::MinContent,
+            3 => MaxTrackSizingFunction::MaxContent,
+            4 => MaxTrackSizingFunction::FitContent(LengthPercentage::Length(number)),
+            5 => MaxTrackSizingFunction::FitContent(LengthPercentage::Percent(number)),
+            6 => MaxTrackSizingFunction::Auto,
+            _ => MaxTrackSizingFunction::Fraction(number),
+        };
+    }
+    changed
+}
+
+fn edit_track(
+    ui: &mut egui::Ui,
+    id_salt: (&str, usize),
+    track: &mut NonRepeatedTrackSizingFunction,
+) -> bool {
+    ui.label("min");
+    let mut changed = edit_min_track(ui, (id_salt, "min"), &mut track.min);
+    ui.label("max");
+    changed |= edit_max_track(ui, (id_salt, "max"), &mut track.max);
+    changed
+}
+
+/// Edit list of tracks with buttons to add or remove tracks
+fn edit_track_list<T>(
+    ui: &mut egui::Ui,
+    tracks: &mut Vec<T>,
+    new_track: impl Fn() -> T,
+    mut edit: impl FnMut(&mut egui::Ui, usize, &mut T) -> bool,
+) -> bool {
+    ui.vertical(|ui| {
+        let mut changed = false;
+        let mut remove = None;
+        for (idx, track) in tracks.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("-").clicked() {
+                    remove = Some(idx);
+                }
+                changed |= edit(ui, idx, track);
+            });
+        }
+        if let Some(idx) = remove {
+            tracks.remove(idx);
+            changed = true;
+        }
+        if ui.small_button("+").clicked() {
+            tracks.push(new_track());
+            changed = true;
+        }
+        changed
+    })
+    .inner
+}
+
+fn edit_tracks(ui: &mut egui::Ui, id_salt: &str, tracks: &mut Vec<TrackSizingFunction>) -> bool {
+    edit_track_list(
+        ui,
+        tracks,
+        || TrackSizingFunction::Single(fr(1.)),
+        |ui, idx, track| match track {
+            TrackSizingFunction::Single(track) => edit_track(ui, (id_salt, idx), track),
+            TrackSizingFunction::Repeat(repetition, tracks) => {
+                // Repeated tracks are shown, but not editable
+                ui.label(format!("repeat({repetition:?}, {tracks:?})"));
+                false
+            }
+        },
+    )
+}
+
+fn edit_auto_tracks(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    tracks: &mut Vec<NonRepeatedTrackSizingFunction>,
+) -> bool {
+    edit_track_list(ui, tracks, auto, |ui, idx, track| {
+        edit_track(ui, (id_salt, idx), track)
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////
+
+/// Generate Rust code that constructs given style.
+///
+/// Only fields that differ from [`taffy::Style::DEFAULT`] are included.
+pub fn style_to_rust_code(style: &Style) -> String {
+    let default = Style::DEFAULT;
+    let mut code = String::from("taffy::Style {\n");
+
+    let mut field = |name: &str, value: String| {
+        code.push_str("    ");
+        code.push_str(name);
+        code.push_str(": ");
+        code.push_str(&value);
+        code.push_str(",\n");
+    };
+
+    macro_rules! fields {
+        ($($name:ident => $to_code:expr),* $(,)?) => {
+            $(
+                if style.$name != default.$name {
+                    field(stringify!($name), $to_code(&style.$name));
+                }
+            )*
+        };
+    }
+
+    fields!(
+        display => |v: &Display| format!("taffy::Display::{v:?}"),
+        item_is_table => |v: &bool| format!("{v}"),
+        box_sizing => |v: &BoxSizing| format!("taffy::BoxSizing::{v:?}"),
+        overflow => |v: &Point<Overflow>| point_code(v, |v| format!("taffy::Overflow::{v:?}")),
+        scrollbar_width => f32_code,
+        position => |v: &Position| format!("taffy::Position::{v:?}"),
+        inset => |v: &Rect<LengthPercentageAuto>| rect_code(v, prefixed_code("LengthPercentageAuto")),
+        size => |v: &Size<Dimension>| size_code(v, prefixed_code("Dimension")),
+        min_size => |v: &Size<Dimension>| size_code(v, prefixed_code("Dimension")),
+        max_size => |v: &Size<Dimension>| size_code(v, prefixed_code("Dimension")),
+        aspect_ratio => |v: &Option<f32>| option_code(v, f32_code),
+        margin => |v: &Rect<LengthPercentageAuto>| rect_code(v, prefixed_code("LengthPercentageAuto")),
+        padding => |v: &Rect<LengthPercentage>| rect_code(v, prefixed_code("LengthPercentage")),
+        border => |v: &Rect<LengthPercentage>| rect_code(v, prefixed_code("LengthPercentage")),
+        align_items => |v: &Option<AlignItems>| option_code(v, prefixed_code("AlignItems")),
+        align_self => |v: &Option<AlignSelf>| option_code(v, prefixed_code("AlignSelf")),
+        justify_items => |v: &Option<AlignItems>| option_code(v, prefixed_code("JustifyItems")),
+        justify_self => |v: &Option<AlignSelf>| option_code(v, prefixed_code("JustifySelf")),
+        align_content => |v: &Option<AlignContent>| option_code(v, prefixed_code("AlignContent")),
+        justify_content => |v: &Option<AlignContent>| option_code(v, prefixed_code("JustifyContent")),
+        gap => |v: &Size<LengthPercentage>| size_code(v, prefixed_code("LengthPercentage")),
+        text_align => |v: &TextAlign| format!("taffy::TextAlign::{v:?}"),
+        flex_direction => |v: &FlexDirection| format!("taffy::FlexDirection::{v:?}"),
+        flex_wrap => |v: &FlexWrap| format!("taffy::FlexWrap::{v:?}"),
+        flex_basis => prefixed_code("Dimension"),
+        flex_grow => f32_code,
+        flex_shrink => f32_code,
+        grid_template_rows => |v: &Vec<TrackSizingFunction>| vec_code(v, track_code),
+        grid_template_columns => |v: &Vec<TrackSizingFunction>| vec_code(v, track_code),
+        grid_auto_rows => |v: &Vec<NonRepeatedTrackSizingFunction>| vec_code(v, non_repeated_track_code),
+        grid_auto_columns => |v: &Vec<NonRepeatedTrackSizingFunction>| vec_code(v, non_repeated_track_code),
+        grid_auto_flow => |v: &GridAutoFlow| format!("taffy::GridAutoFlow::{v:?}"),
+        grid_row => |v: &Line<GridPlacement>| line_code(v),
+        grid_column => |v: &Line<GridPlacement>| line_code(v),
+    );
+
+    code.push_str("    ..Default::default()\n}");
+    code
+}
+
+fn f32_code(value: &f32) -> String {
+    format!("{value:?}")
+}
+
+/// Code for simple enum types which Debug representation matches Rust code
+fn prefixed_code<T: Debug>(type_name: &'static str) -> impl Fn(&T) -> String {
+    move |value| format!("taffy::{type_name}::{value:?}")
+}
+
+fn option_code<T>(value: &Option<T>, to_code: impl Fn(&T) -> String) -> String {
+    match value {
+        Some(value) => format!("Some({})", to_code(value)),
+        None => "None".to_owned(),
+    }
+}
+
+fn rect_code<T>(rect: &Rect<T>, to_code: impl Fn(&T) -> String) -> String {
+    format!(
+        "taffy::Rect {{ left: {}, right: {}, top: {}, bottom: {} }}",
+        to_code(&rect.left),
+        to_code(&rect.right),
+        to_code(&rect.top),
+        to_code(&rect.bottom)
+    )
+}
+
+fn size_code<T>(size: &Size<T>, to_code: impl Fn(&T) -> String) -> String {
+    format!(
+        "taffy::Size {{ width: {}, height: {} }}",
+        to_code(&size.width),
+        to_code(&size.height)
+    )
+}
+
+fn point_code<T>(point: &Point<T>, to_code: impl Fn(&T) -> String) -> String {
+    format!(
+        "taffy::Point {{ x: {}, y: {} }}",
+        to_code(&point.x),
+        to_code(&point.y)
+    )
+}
+
+fn vec_code<T>(values: &[T], to_code: impl Fn(&T) -> String) -> String {
+    let values: Vec<String> = values.iter().map(to_code).collect();
+    format!("vec![{}]", values.join(", "))
+}
+
+fn line_code(line: &Line<GridPlacement>) -> String {
+    let placement_code = |placement: &GridPlacement| match placement {
+        GridPlacement::Auto => "taffy::GridPlacement::Auto".to_owned(),
+        GridPlacement::Line(line) => format!("taffy::style_helpers::line({})", line.as_i16()),
+        GridPlacement::Span(span) => format!("taffy::GridPlacement::Span({span})"),
+    };
+    format!(
+        "taffy::Line {{ start: {}, end: {} }}",
+        placement_code(&line.start),
+        placement_code(&line.end)
+    )
+}
+
+fn min_track_code(value: &MinTrackSizingFunction) -> String {
+    match value {
+        MinTrackSizingFunction::Fixed(value) => {
+            format!("taffy::MinTrackSizingFunction::Fixed(taffy::LengthPercentage::{value:?})")
+        }
+        value => format!("taffy::MinTrackSizingFunction::{value:?}"),
+    }
+}
+
+fn max_track_code(value: &MaxTrackSizingFunction) -> String {
+    match value {
+        MaxTrackSizingFunction::Fixed(value) => {
+            format!("taffy::MaxTrackSizingFunction::Fixed(taffy::LengthPercentage::{value:?})")
+        }
+        MaxTrackSizingFunction::FitContent(value) => {
+            format!("taffy::MaxTrackSizingFunction::FitContent(taffy::LengthPercentage::{value:?})")
+        }
+        value => format!("taffy::MaxTrackSizingFunction::{value:?}"),
+    }
+}
+
+fn non_repeated_track_code(track: &NonRepeatedTrackSizingFunction) -> String {
+    format!(
+        "taffy::style_helpers::minmax({}, {})",
+        min_track_code(&track.min),
+        max_track_code(&track.max)
+    )
+}
+
+fn track_code(track: &TrackSizingFunction) -> String {
+    match track {
+        TrackSizingFunction::Single(track) => format!(
+            "taffy::TrackSizingFunction::Single({})",
+            non_repeated_track_code(track)
+        ),
+        TrackSizingFunction::Repeat(repetition, tracks) => format!(
+            "taffy::TrackSizingFunction::Repeat(taffy::GridTrackRepetition::{:?}, {})",
+            repetition,
+            vec_code(tracks, non_repeated_track_code)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assert that generated code matches the given style expression (ignoring whitespace),
+    /// so generated code is known to compile and construct the same style
+    macro_rules! assert_style_code {
+        ($style:expr) => {{
+            let style: Style = $style;
+            let strip = |code: &str| code.split_whitespace().collect::<String>();
+            assert_eq!(
+                strip(&style_to_rust_code(&style)),
+                strip(stringify!($style)),
+                "{}",
+                style_to_rust_code(&style)
+            );
+        }};
+    }
+
+    #[test]
+    fn default_style_code() {
+        assert_style_code!(taffy::Style {
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn grid_tracks_code() {
+        assert_style_code!(taffy::Style {
+            display: taffy::Display::Grid,
+            grid_template_columns: vec![
+                taffy::TrackSizingFunction::Single(taffy::style_helpers::minmax(
+                    taffy::MinTrackSizingFunction::Fixed(taffy::LengthPercentage::Length(10.0)),
+                    taffy::MaxTrackSizingFunction::Fraction(1.0)
+                )),
+                taffy::TrackSizingFunction::Repeat(
+                    taffy::GridTrackRepetition::Count(2),
+                    vec![taffy::style_helpers::minmax(
+                        taffy::MinTrackSizingFunction::Auto,
+                        taffy::MaxTrackSizingFunction::MinContent
+                    )]
+                )
+            ],
+            grid_auto_rows: vec![taffy::style_helpers::minmax(
+                taffy::MinTrackSizingFunction::MinContent,
+                taffy::MaxTrackSizingFunction::FitContent(taffy::LengthPercentage::Percent(0.5))
+            )],
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn grid_placement_code() {
+        assert_style_code!(taffy::Style {
+            grid_row: taffy::Line {
+                start: taffy::style_helpers::line(2),
+                end: taffy::GridPlacement::Span(3)
+            },
+            grid_column: taffy::Line {
+                start: taffy::style_helpers::line(-1),
+                end: taffy::GridPlacement::Auto
+            },
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn alignment_code() {
+        assert_style_code!(taffy::Style {
+            align_items: Some(taffy::AlignItems::Center),
+            justify_content: Some(taffy::JustifyContent::SpaceBetween),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn inset_rect_code() {
+        assert_style_code!(taffy::Style {
+            inset: taffy::Rect {
+                left: taffy::LengthPercentageAuto::Length(10.0),
+                right: taffy::LengthPercentageAuto::Auto,
+                top: taffy::LengthPercentageAuto::Percent(0.5),
+                bottom: taffy::LengthPercentageAuto::Auto
+            },
+            ..Default::default()
+        });
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use crate::{NodeStyle, SharedStyle};
+
+/// Distinct node styles of layout state, equal styles of different nodes are stored once
+#[derive(Default)]
+pub(crate) struct StyleInterner {
+    /// Interned styles by style value hash
+    styles: HashMap<u64, Vec<SharedStyle>>,
+}
+
+/// Style stored in [`StyleInterner`]
+#[derive(Clone)]
+pub(crate) struct InternedStyle {
+    pub style: SharedStyle,
+    hash: u64,
+}
+
+impl StyleInterner {
+    /// Interned style equal to the given style.
+    ///
+    /// Style is added if equal style is not interned yet. Returned style must be released
+    /// when it is not used by node anymore (See [`StyleInterner::release`]).
+    pub(crate) fn intern(&mut self, style: NodeStyle) -> InternedStyle {
+        let hash = style_hash(style.style());
+        let bucket = self.styles.entry(hash).or_default();
+        let equal = |interned: &&SharedStyle| match &style {
+            NodeStyle::Shared(style) if interned.ptr_eq(style) => true,
+            style => interned.style() == style.style(),
+        };
+        let interned = match bucket.iter().find(equal) {
+            Some(interned) => interned.clone(),
+            None => {
+                let style = match style {
+                    NodeStyle::Owned(style) => SharedStyle::new(style),
+                    NodeStyle::Shared(style) => style,
+                };
+                bucket.push(style.clone());
+                style
+            }
+        };
+        InternedStyle {
+            style: interned,
+            hash,
+        }
+    }
+
+    /// Release style that is not used by node anymore.
+    /// Styles of the same hash that are not used anymore are dropped.
+    pub(crate) fn release(&mut self, style: InternedStyle) {
+        let InternedStyle { style, hash } = style;
+        drop(style);
+
+        let std::collections::hash_map::Entry::Occupied(mut bucket) = self.styles.entry(hash)
+        else {
+            return;
+        };
+        // Style is not used if it is referenced only by the interner
+        bucket
+            .get_mut()
+            .retain(|interned| Arc::strong_count(&interned.0) > 1);
+        if bucket.get().is_empty() {
+            bucket.remove();
+        }
+    }
+
+    /// Number of distinct interned styles
+    pub(crate) fn len(&self) -> usize {
+        self.styles.values().map(Vec::len).sum()
+    }
+}
+
+/// Hash of style value (Style is not hashable because of floating point values)
+fn style_hash(style: &taffy::Style) -> u64 {
+    struct HashWriter<H>(H);
+
+    impl<H: Hasher> std::fmt::Write for HashWriter<H> {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            self.0.write(s.as_bytes());
+            Ok(())
+        }
+    }
+
+    let mut writer = HashWriter(std::collections::hash_map::DefaultHasher::new());
+    // Formatting to hasher can not fail
+    let _ = std::fmt::write(&mut writer, format_args!("{style:?}"));
+    writer.0.finish()
+}
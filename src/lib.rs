@@ -6,10 +6,16 @@ use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 
+use animation::{LayoutAnimation, NodeAnimation};
+use convergence::{ConvergencePolicy, ConvergenceReport, ConvergenceStrategy, NodeChangeKind};
 use egui::util::IdTypeMap;
 use egui::{Pos2, Response, Ui, UiBuilder};
+use intern::{InternedStyle, StyleInterner};
+use memo::{MemoEnv, MemoState};
 use parking_lot::{ArcMutexGuard, RawMutex};
+use scroll::{ScrollConfig, ScrollInfo, ScrollRequest};
 use taffy::prelude::*;
+use transition::{NodeTransition, TransitionPhase, TransitionState};
 use widgets::TaffySeparator;
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -28,6 +34,44 @@ pub mod virtual_tui;
 /// Custom background implementations to paint UI node backgrounds
 pub mod bg;
 
+/// Keyed reconciliation of taffy node children between frames
+mod reconcile;
+
+/// Layout debug overlay painting
+mod debug;
+
+/// Live layout inspector to browse taffy node tree and edit node styles
+pub mod inspector;
+
+/// Export of computed layout tree in stable textual formats
+pub mod snapshot;
+
+/// Frame-to-frame layout animation
+pub mod animation;
+
+/// Layout convergence policy and diagnostics
+pub mod convergence;
+
+/// Enter and exit transitions for conditionally shown nodes
+pub mod transition;
+
+/// Measured layout persistence across application restarts
+#[cfg(feature = "serde")]
+mod persistence;
+
+/// Memoized node content
+mod memo;
+
+/// Node style interning
+mod intern;
+
+/// Scroll state, programmatic scrolling and sticky positioning
+pub mod scroll;
+
+/// Headless layout test harness
+#[cfg(feature = "test_support")]
+pub mod testing;
+
 /// Helper function to initialize taffy layout
 pub fn tui(ui: &mut egui::Ui, id: impl Into<egui::Id>) -> TuiInitializer<'_> {
     TuiInitializer {
@@ -43,6 +87,11 @@ pub fn tui(ui: &mut egui::Ui, id: impl Into<egui::Id>) -> TuiInitializer<'_> {
             width: None,
             height: None,
         },
+        debug_overlay: false,
+        convergence: Default::default(),
+        double_buffer: false,
+        #[cfg(feature = "serde")]
+        persist_layout: false,
     }
 }
 
@@ -55,6 +104,11 @@ pub struct TuiInitializer<'a> {
     known_size: Size<Option<f32>>,
     style: taffy::Style,
     id: egui::Id,
+    debug_overlay: bool,
+    convergence: ConvergencePolicy,
+    double_buffer: bool,
+    #[cfg(feature = "serde")]
+    persist_layout: bool,
 }
 
 impl<'a> TuiInitializer<'a> {
@@ -121,16 +175,80 @@ impl<'a> TuiInitializer<'a> {
         self
     }
 
+    /// Paint layout debug overlay for every node (margin, border, padding and content rectangles)
+    /// and show information about hovered node.
+    ///
+    /// See [`Tui::set_debug_overlay`].
+    pub fn debug_overlay(mut self, enabled: bool) -> TuiInitializer<'a> {
+        self.debug_overlay = enabled;
+        self
+    }
+
+    /// Set layout convergence policy: how many extra passes may be requested in one frame
+    /// and whether layout changes are applied in the same frame or in the next frame.
+    ///
+    /// Convergence of the last frame can be inspected with [`TaffyState::convergence_report`].
+    pub fn convergence(mut self, policy: ConvergencePolicy) -> TuiInitializer<'a> {
+        self.convergence = policy;
+        self
+    }
+
+    /// Keep the last settled layout painted while layout changes are being measured.
+    ///
+    /// New leaf nodes are measured invisibly in their first frame and layout may need
+    /// additional passes to settle. With double buffering, passes that show unsettled layout
+    /// paint the previous settled layout instead, content is switched atomically
+    /// once the layout settles. If layout does not settle for several frames,
+    /// current layout is shown.
+    ///
+    /// Interaction with Tui content is blocked while the previous layout is painted.
+    /// Stuck nodes (See [`TuiBuilderLogic::position_sticky`]) are painted in the Tui layer.
+    pub fn double_buffer(mut self, enabled: bool) -> TuiInitializer<'a> {
+        self.double_buffer = enabled;
+        self
+    }
+
+    /// Persist measured layout (node styles, leaf measurements and tree structure)
+    /// in egui persisted memory (See [`egui::util::IdTypeMap::insert_persisted`]).
+    ///
+    /// Stored layout is restored when layout state does not exist yet, for example,
+    /// after application restart, therefore tui is shown fully laid out in the first frame.
+    /// Layout is not restored after explicit reset (See [`TaffyState::reset`]).
+    ///
+    /// Layout is stored when it settles after recalculation and saved to disk
+    /// by egui integration when egui `persistence` feature is enabled.
+    ///
+    /// Nodes measured with [`TuiMeasure`] are measured again.
+    #[cfg(feature = "serde")]
+    pub fn persist_layout(mut self, enabled: bool) -> TuiInitializer<'a> {
+        self.persist_layout = enabled;
+        self
+    }
+
     /// Show tui
     pub fn show<T>(self, f: impl FnOnce(&mut Tui) -> T) -> T {
         let ui = self.ui;
-        let output = Tui::create(
+        let debug_overlay = self.debug_overlay;
+        let convergence = self.convergence;
+        let double_buffer = self.double_buffer;
+        let options = TuiCreateOptions {
+            measure_phase: false,
+            #[cfg(feature = "serde")]
+            persist_layout: self.persist_layout,
+        };
+        let (output, _) = Tui::create_phase(
             ui,
             self.id,
             ui.available_rect_before_wrap(),
             Some(self.available_space),
             self.style,
-            f,
+            options,
+            |tui| {
+                tui.set_debug_overlay(debug_overlay);
+                tui.set_convergence_policy(convergence);
+                tui.set_double_buffer(double_buffer);
+                f(tui)
+            },
         );
 
         if self.allocated_rect.is_none() {
@@ -143,6 +261,63 @@ impl<'a> TuiInitializer<'a> {
         }
         output.inner
     }
+
+    /// Show tui using two phases in the same pass.
+    ///
+    /// First phase runs given closure in invisible and disabled ui to record node tree and
+    /// leaf measurements, then taffy calculates the layout. Second phase runs closure again
+    /// and paints every node at its final position. Layout changes are shown in the same pass
+    /// without waiting for the next pass or frame.
+    ///
+    /// Closure is called twice, therefore it should not have side effects that depend on
+    /// the number of calls. Egui widgets in the first phase get different ids.
+    ///
+    /// Leaf measurements of new nodes depend on the layout, therefore measure phase is
+    /// repeated while layout changes (Limited by [`ConvergencePolicy::max_extra_passes`]).
+    pub fn show_two_phase<T>(self, mut f: impl FnMut(&mut Tui) -> T) -> T {
+        let rect = self.ui.available_rect_before_wrap();
+        let mut measure_ui = self.ui.new_child(
+            UiBuilder::new()
+                .id_salt("egui_taffy_measure_phase")
+                .max_rect(rect)
+                .invisible(),
+        );
+
+        let options = TuiCreateOptions {
+            measure_phase: true,
+            #[cfg(feature = "serde")]
+            persist_layout: self.persist_layout,
+        };
+
+        for _ in 0..=self.convergence.max_extra_passes {
+            let (_, layout_changed) = Tui::create_phase(
+                &mut measure_ui,
+                self.id,
+                rect,
+                Some(self.available_space),
+                self.style.clone(),
+                options,
+                |tui| {
+                    f(tui);
+                },
+            );
+            if !layout_changed {
+                break;
+            }
+        }
+
+        self.show(f)
+    }
+}
+
+/// Tui options that are applied before the layout closure is called
+#[derive(Clone, Copy, Default)]
+struct TuiCreateOptions {
+    /// Record node tree and measurements without painting (See [`TuiInitializer::show_two_phase`])
+    measure_phase: bool,
+    /// Restore and store measured layout (See [`TuiInitializer::persist_layout`])
+    #[cfg(feature = "serde")]
+    persist_layout: bool,
 }
 
 /// Tui (Egui Taffy UI) is used to place ui nodes and set their id, style configuration
@@ -156,8 +331,20 @@ pub struct Tui {
     current_id: egui::Id,
     current_node: Option<NodeId>,
     current_node_index: usize,
+    /// Children placed in the current node during this frame
+    current_children: Vec<NodeId>,
+    /// Reusable child list allocations
+    children_buffers: Vec<Vec<NodeId>>,
+    /// First painted shape index of nodes, recorded while exit transitions may run
+    /// (See [`Tui::keep_exiting_children`])
+    child_first_shapes: HashMap<NodeId, usize>,
+    /// Number of ancestor nodes whose painted shapes are stored for memo or exit transition
+    capturing_ancestors: usize,
     current_viewport: egui::Rect,
     current_viewport_content: egui::Rect,
+    /// Visible area of the nearest scrollable ancestor node or Tui ui clip rect
+    /// (See [`TuiBuilderLogic::position_sticky`])
+    current_scroll_viewport: egui::Rect,
     current_rect: egui::Rect,
     taffy_container: TaffyContainerUi,
 
@@ -168,6 +355,28 @@ pub struct Tui {
 
     state: ArcMutexGuard<RawMutex, TaffyState>,
 
+    /// Paint layout debug overlay
+    debug_overlay: bool,
+    /// Debug information of hovered node is already shown
+    debug_overlay_hover_shown: bool,
+
+    convergence: ConvergencePolicy,
+    /// egui pass index in the current frame
+    pass_index: usize,
+
+    double_buffer: bool,
+    /// New nodes were added during this pass
+    added_nodes: bool,
+    /// Tui records node tree and measurements without painting (See [`TuiInitializer::show_two_phase`])
+    measure_phase: bool,
+    /// Layout was recalculated in this pass
+    layout_changed: bool,
+    /// Drop cached layout state at the end of this pass
+    reset_state: bool,
+    /// Store measured layout in egui persisted memory
+    #[cfg(feature = "serde")]
+    persist_layout: bool,
+
     /// Due to how egui style works with deeply nested structures,
     /// to avoid large amount of [`egui::Style`]` copies
     /// we can cache some style changes
@@ -190,6 +399,21 @@ impl Tui {
         style: Style,
         f: impl FnOnce(&mut Tui) -> T,
     ) -> TaffyReturn<T> {
+        let options = TuiCreateOptions::default();
+        Self::create_phase(ui, id, root_rect, available_space, style, options, f).0
+    }
+
+    /// Create Tui, returns if layout was recalculated
+    fn create_phase<T>(
+        ui: &mut Ui,
+        id: egui::Id,
+        root_rect: egui::Rect,
+        available_space: Option<Size<AvailableSpace>>,
+        style: Style,
+        options: TuiCreateOptions,
+        f: impl FnOnce(&mut Tui) -> T,
+    ) -> (TaffyReturn<T>, bool) {
+        let measure_phase = options.measure_phase;
         let ui = ui.new_child(UiBuilder::new());
 
         // Create stored state
@@ -201,10 +425,17 @@ impl Tui {
                 .clone();
             state
         });
-        let state = state
+        let mut state = state
             .try_lock_arc()
             .expect("Each egui_taffy instance should have unique id");
 
+        let pass_index = ui.ctx().current_pass_index();
+        state
+            .report
+            .begin_pass(ui.ctx().cumulative_frame_nr(), measure_phase);
+        state.generation += 1;
+        let clip_rect = ui.clip_rect();
+
         let mut this = Self {
             main_id: id,
 
@@ -213,19 +444,44 @@ impl Tui {
             ui,
             current_node: None,
             current_node_index: 0,
+            current_children: Vec::new(),
+            child_first_shapes: HashMap::new(),
+            capturing_ancestors: 0,
+            children_buffers: Vec::new(),
             current_rect: root_rect,
             current_viewport: root_rect,
             current_viewport_content: root_rect,
+            current_scroll_viewport: clip_rect,
             taffy_container: Default::default(),
             root_rect,
             available_space,
             current_id: id,
             last_scroll_offset: egui::Vec2::ZERO,
             state,
+            debug_overlay: false,
+            debug_overlay_hover_shown: false,
+            convergence: Default::default(),
+            pass_index,
+            double_buffer: false,
+            added_nodes: false,
+            measure_phase,
+            layout_changed: false,
+            reset_state: false,
+            #[cfg(feature = "serde")]
+            persist_layout: options.persist_layout,
             interactive_container_inactive_style_cache: Default::default(),
         };
 
-        let res = this.tui().id(id).style(style).add(|state| {
+        #[cfg(feature = "serde")]
+        if std::mem::take(&mut this.state.restore_layout_cache) && this.persist_layout {
+            // Layout state was just created
+            this.restore_layout_cache();
+        }
+
+        let first_shape = this.next_shape_idx();
+
+        let tui = this.tui().id(id).style(style).unpack();
+        let res = tui.builder_tui.add_child(tui.params, (), |state, _| {
             let resp = f(state);
             let container = state.recalculate();
             TaffyReturn {
@@ -233,81 +489,129 @@ impl Tui {
                 container,
             }
         });
+        let res = res.main;
+
+        this.double_buffer_paint(first_shape);
+
+        if !measure_phase && !this.state.scroll_requests.is_empty() {
+            // Nodes of pending requests may be shown in the next pass
+            this.state.expire_scroll_requests();
+            this.ui.ctx().request_repaint();
+        }
+
+        if this.reset_state {
+            this.state.reset();
+            this.ui.ctx().request_repaint();
+        }
 
         log::trace!(
             "Cached {} interactive styles!",
             this.interactive_container_inactive_style_cache.len()
         );
 
-        res
+        (res, this.layout_changed)
     }
 
     /// Add taffy child node, correctly update taffy tree state
     fn add_child_node(
         &mut self,
         id: egui::Id,
-        style: taffy::Style,
+        style: NodeStyle,
         sticky: egui::Vec2b,
     ) -> (NodeId, TaffyContainerUi) {
-        let child_idx = self.current_node_index;
         self.current_node_index += 1;
 
         let mut first_frame = false;
 
+        let first_shape = if self.state.transitions.is_empty() {
+            None
+        } else {
+            Some(self.next_shape_idx())
+        };
+
         let state: &mut TaffyState = &mut self.state;
 
         let node_id = match state.id_to_node_id.entry(id) {
             std::collections::hash_map::Entry::Occupied(mut occupied_entry) => {
                 let val = occupied_entry.get_mut();
 
-                if val.keep {
+                if val.generation == state.generation {
                     log::error!("Taffy layout id collision!");
                 }
-                val.keep = true;
+                val.generation = state.generation;
+                val.sticky = sticky;
+                val.stuck = egui::Vec2b::FALSE;
+                val.scroll_offset = egui::Vec2::ZERO;
 
                 let node_id = val.node_id;
-                if state.taffy_tree.style(node_id).unwrap() != &style {
-                    state.taffy_tree.set_style(node_id, style).unwrap();
+
+                let unchanged = match (&style, &val.style) {
+                    // Shared style has not changed since the last frame
+                    (NodeStyle::Shared(style), Some(last)) => style.ptr_eq(&last.style),
+                    (NodeStyle::Owned(style), Some(last)) => style == last.style.style(),
+                    _ => false,
+                };
+                if !unchanged {
+                    let style = state.styles.intern(style);
+                    if state.taffy_tree.style(node_id).unwrap() != style.style.style() {
+                        state
+                            .report
+                            .record(self.pass_index, id, NodeChangeKind::Style);
+                        state
+                            .taffy_tree
+                            .set_style(node_id, style.style.style().clone())
+                            .unwrap();
+                    }
+                    if let Some(last) = val.style.replace(style) {
+                        state.styles.release(last);
+                    }
+                }
+                if val.scroll_info.is_some() {
+                    let overflow = state.taffy_tree.style(node_id).unwrap().overflow;
+                    if overflow.x != taffy::Overflow::Scroll
+                        && overflow.y != taffy::Overflow::Scroll
+                    {
+                        // Node is not scrollable anymore
+                        val.scroll_info = None;
+                    }
                 }
                 node_id
             }
             std::collections::hash_map::Entry::Vacant(vacant_entry) => {
                 first_frame = true;
-                let node_id = state.taffy_tree.new_leaf(style).unwrap();
+                self.added_nodes = true;
+                state
+                    .report
+                    .record(self.pass_index, id, NodeChangeKind::Added);
+                let style = state.styles.intern(style);
+                let node_id = state
+                    .taffy_tree
+                    .new_leaf(style.style.style().clone())
+                    .unwrap();
+                state.node_to_id.insert(node_id, id);
                 vacant_entry.insert(NodeData {
                     node_id,
-                    keep: true,
+                    generation: state.generation,
+                    sticky,
+                    stuck: egui::Vec2b::FALSE,
+                    scroll_offset: egui::Vec2::ZERO,
+                    scroll_info: None,
+                    rect: egui::Rect::NOTHING,
+                    animation: None,
+                    style: Some(style),
+                    sticky_inset: None,
                 });
                 node_id
             }
         };
 
-        if let Some(current_node) = self.current_node {
-            if child_idx < state.taffy_tree.child_count(current_node) {
-                // Check if child at position matches
-                if state
-                    .taffy_tree
-                    .child_at_index(current_node, child_idx)
-                    .unwrap()
-                    != node_id
-                {
-                    // Layout has changed, remove all following children
-                    //
-                    // Because node one by one removal is slow if items have changed their location.
-                    // Faster is to remove whole tail.
-                    let count = state.taffy_tree.child_count(current_node);
-                    state
-                        .taffy_tree
-                        .remove_children_range(current_node, child_idx..count)
-                        .unwrap();
-
-                    // Add element to the end
-                    state.taffy_tree.add_child(current_node, node_id).unwrap();
-                }
-            } else {
-                // Add element to the end
-                state.taffy_tree.add_child(current_node, node_id).unwrap();
-            }
+        if self.current_node.is_some() {
+            // Children are attached to the parent node after all of them are known.
+            // See [`Tui::reconcile_current_children`]
+            self.current_children.push(node_id);
+        }
+        if let Some(first_shape) = first_shape {
+            self.child_first_shapes.insert(node_id, first_shape);
         }
 
         let container = TaffyContainerUi {
@@ -315,6 +619,7 @@ impl Tui {
             parent_rect: self.current_rect,
             first_frame,
             sticky,
+            sticky_delta: egui::Vec2::ZERO,
             last_scroll_offset: self.last_scroll_offset,
         };
 
@@ -329,16 +634,56 @@ impl Tui {
         background_draw: B,
         f: impl FnOnce(&mut Tui, &mut <B as BackgroundDraw>::ReturnValue) -> FR,
     ) -> TaffyMainBackgroundReturnValues<FR, B::ReturnValue>
+    where
+        B: BackgroundDraw,
+    {
+        self.add_child_skippable(params, background_draw, f, None)
+    }
+
+    /// Add child taffy node to the layout. Content of node with `display: None` style
+    /// is skipped and `None` is returned, node keeps its child nodes in the taffy tree.
+    #[inline]
+    fn add_child_displayed<FR, B>(
+        &mut self,
+        params: TuiBuilderParams,
+        background_draw: B,
+        f: impl FnOnce(&mut Tui, &mut <B as BackgroundDraw>::ReturnValue) -> FR,
+    ) -> TaffyMainBackgroundReturnValues<Option<FR>, B::ReturnValue>
+    where
+        B: BackgroundDraw,
+    {
+        self.add_child_skippable(
+            params,
+            background_draw,
+            |tui, bg| Some(f(tui, bg)),
+            Some((ContentSkip::DisplayNone, None)),
+        )
+    }
+
+    /// Add child taffy node to the layout. If `skip` is provided, node content
+    /// is not shown when skip condition is met and provided value is returned instead.
+    #[inline]
+    fn add_child_skippable<FR, B>(
+        &mut self,
+        params: TuiBuilderParams,
+        background_draw: B,
+        f: impl FnOnce(&mut Tui, &mut <B as BackgroundDraw>::ReturnValue) -> FR,
+        skip: Option<(ContentSkip, FR)>,
+    ) -> TaffyMainBackgroundReturnValues<FR, B::ReturnValue>
     where
         B: BackgroundDraw,
     {
         let mut background_slot = stackbox::Slot::VACANT;
         let mut ui_slot = stackbox::Slot::VACANT;
 
+        let mut params = params;
+        params.layout_only &= B::IS_EMPTY;
+
         self.add_child_dyn(
             params,
             background_slot.stackbox(background_draw).into_dyn(),
             ui_slot.stackbox(f).into_dyn(),
+            skip,
         )
     }
 
@@ -347,29 +692,89 @@ impl Tui {
         params: TuiBuilderParams,
         background_draw: StackBoxDynBackgroundDrawDyn<BR>,
         f: StackBoxDynFnOnceTuiUi<BR, FR>,
+        skip: Option<(ContentSkip, FR)>,
     ) -> TaffyMainBackgroundReturnValues<FR, BR> {
         let TuiBuilderParams {
             id,
             style,
+            shared_style,
             disabled,
             wrap_mode,
             egui_style,
             layout,
             sticky,
+            animation,
+            transition,
+            layout_only,
+            visibility,
+            scroll_config,
+            position_sticky,
         } = params;
 
-        let style = style.unwrap_or_default();
-
+        let tui_id_kind = id.kind_name();
         let id = id.resolve(self);
 
-        let overflow_style = style.overflow;
+        // Style override replaces the application style (See [`TaffyState::set_style_override`])
+        let (style, shared_style) = match self.state.style_overrides.get(&id) {
+            Some(style_override) => (Some(style_override.clone()), None),
+            None => (style, shared_style),
+        };
+
+        let (style, mut opacity, sticky_inset) = match (style, shared_style) {
+            (None, Some(shared_style)) if transition.is_none() && !position_sticky => {
+                self.remove_transition(id);
+                (NodeStyle::Shared(shared_style), 1., None)
+            }
+            (style, shared_style) => {
+                let mut style = style
+                    .or_else(|| shared_style.map(|style| style.style().clone()))
+                    .unwrap_or_default();
+                let opacity = self.update_transition(id, transition, &mut style);
+                // Sticky node is laid out at its normal position, inset is used as
+                // sticky threshold instead of relative offset
+                let sticky_inset = position_sticky
+                    .then(|| std::mem::replace(&mut style.inset, taffy::Rect::auto()));
+                (NodeStyle::Owned(style), opacity, sticky_inset)
+            }
+        };
+
+        let overflow_style = style.style().overflow;
 
         let (node_id, mut current_taffy_container) = self.add_child_node(id, style, sticky);
+        let display_none = self.state.taffy_tree.style(node_id).unwrap().display == Display::None;
+        let hidden = display_none || visibility == Visibility::Hidden;
+        self.animate_layout(id, animation, &mut current_taffy_container.layout);
+        if let Some(node_data) = self.state.id_to_node_id.get_mut(&id) {
+            node_data.sticky_inset = sticky_inset;
+        }
+        if let Some(inset) = sticky_inset {
+            self.apply_position_sticky(id, inset, &mut current_taffy_container);
+        }
+        if !self.measure_phase {
+            if let Some(node_data) = self.state.id_to_node_id.get_mut(&id) {
+                node_data.rect = current_taffy_container.full_container();
+            }
+        }
+
+        if let Some(transition) = transition {
+            if current_taffy_container.first_frame {
+                // New node, start enter transition
+                let time = self.ui.input(|i| i.time);
+                let state = TransitionState::new(id, transition, TransitionPhase::Enter, time);
+                opacity = state.opacity(0.);
+                self.state.transitions.insert(node_id, state);
+                self.ui.ctx().request_repaint();
+            }
+        }
 
         let stored_id = self.current_id;
         let stored_node = self.current_node;
         let stored_current_node_index = self.current_node_index;
         let stored_current_rect = self.current_rect;
+        let stored_children = std::mem::replace(
+            &mut self.current_children,
+            self.children_buffers.pop().unwrap_or_default(),
+        );
 
         std::mem::swap(&mut current_taffy_container, &mut self.taffy_container);
         let stored_taffy_container = current_taffy_container;
@@ -387,149 +792,301 @@ impl Tui {
         self.current_node_index = 0;
         self.current_rect = self.taffy_container.full_container();
 
-        let mut ui_builder = egui::UiBuilder::new()
-            .id_salt(id.with("_ui"))
-            // This does not set clipping, therefore we can still paint outside child ui
-            // (on border) and avoid initialising two child user interfaces
-            .max_rect(full_container_without_border);
-
-        ui_builder.style = egui_style;
-        ui_builder.layout = layout;
-        ui_builder.disabled = disabled;
-
-        let mut child_ui = self.ui.new_child(ui_builder);
-        child_ui.expand_to_include_rect(full_container_without_border);
+        if !self.state.scroll_requests.is_empty() {
+            self.handle_scroll_request(id);
+        }
 
-        if let Some(wrap_mode) = wrap_mode {
-            if child_ui.style().wrap_mode != Some(wrap_mode) {
-                child_ui.style_mut().wrap_mode = Some(wrap_mode);
-            }
+        let memo_key = match &skip {
+            Some((ContentSkip::Memo(key), _)) => Some(*key),
+            _ => None,
+        };
+        // Stuck node is painted and interacted with above the content that follows it.
+        // Shapes stored by double buffering and by ancestors are taken only from the Tui layer,
+        // so node stays in it while they are recorded.
+        let raise_stuck = !self.double_buffer && self.capturing_ancestors == 0;
+        let mut parent_ui = (raise_stuck && self.taffy_container.stuck().any())
+            .then(|| self.sticky_ui(id))
+            .flatten();
+        if let Some(sticky_ui) = &mut parent_ui {
+            std::mem::swap(sticky_ui, &mut self.ui);
+            // Exiting siblings are not moved below shapes of the other layer
+            self.child_first_shapes.remove(&node_id);
         }
 
-        let mut bg = background_draw.draw_dyn(&mut child_ui, &self.taffy_container);
+        let memo_env = memo_key.map(|_| MemoEnv {
+            style: self.ui.style().clone(),
+            node_style: egui_style.clone(),
+            wrap_mode,
+            pixels_per_point: self.ui.ctx().pixels_per_point(),
+        });
 
-        let fg = {
-            let mut scroll_in_directions = egui::Vec2b::FALSE;
-            match overflow_style.y {
-                taffy::Overflow::Visible => {
-                    // Do nothing
-                }
-                taffy::Overflow::Clip | taffy::Overflow::Hidden | taffy::Overflow::Scroll => {
-                    // Add scroll area
-                    if overflow_style.y == taffy::Overflow::Scroll {
-                        scroll_in_directions.y = true;
-                    }
+        // Painted node content is stored to replay it during exit transition
+        // or when memoized node is shown again
+        let first_shape =
+            (transition.is_some() || memo_key.is_some()).then(|| self.next_shape_idx());
+        if first_shape.is_some() {
+            self.capturing_ancestors += 1;
+        }
 
-                    // Hide overflow
-                    let mut clip_rect = child_ui.clip_rect();
-                    clip_rect.min.y = full_container_without_border.min.y;
-                    clip_rect.max.y = full_container_without_border.max.y;
-                    child_ui.shrink_clip_rect(clip_rect);
+        // Layout only node places its children directly in the parent ui
+        let layout_only = layout_only
+            && !hidden
+            && !disabled
+            && wrap_mode.is_none()
+            && egui_style.is_none()
+            && layout.is_none()
+            && transition.is_none()
+            && overflow_style.x == taffy::Overflow::Visible
+            && overflow_style.y == taffy::Overflow::Visible;
+
+        let skipped_value = skip.and_then(|(condition, value)| {
+            let skip = match condition {
+                ContentSkip::DisplayNone => display_none,
+                // Content of hidden node is not needed
+                condition => hidden || self.skip_content(node_id, condition, memo_env.as_ref()),
+            };
+            skip.then_some(value)
+        });
+        let skipped = skipped_value.is_some();
+
+        let (fg, bg) = if let Some(fg) = skipped_value {
+            let bg = if hidden {
+                // Background of hidden node is not painted and can not be interacted with
+                let mut hidden_ui = self
+                    .ui
+                    .new_child(egui::UiBuilder::new().id_salt(id.with("_ui")).invisible());
+                background_draw.draw_dyn(&mut hidden_ui, &self.taffy_container)
+            } else {
+                background_draw.draw_dyn(&mut self.ui, &self.taffy_container)
+            };
+            if !hidden && !self.state.scroll_requests.is_empty() {
+                self.handle_skipped_scroll_requests(node_id);
+            }
+            (fg, bg)
+        } else if layout_only {
+            let mut bg = background_draw.draw_dyn(&mut self.ui, &self.taffy_container);
+            let fg = f.show_dyn(self, &mut bg);
+            (fg, bg)
+        } else {
+            // Scroll area id is derived from the child ui id, scroll area id salt
+            // replaces node id to keep scroll state when node id changes.
+            // New node has no layout yet, its scroll area would clamp the stored scroll offset.
+            let ui_id_salt = match scroll_config.and_then(|config| config.id_salt) {
+                Some(id_salt)
+                    if !self.taffy_container.first_frame
+                        && (overflow_style.x == taffy::Overflow::Scroll
+                            || overflow_style.y == taffy::Overflow::Scroll) =>
+                {
+                    id_salt
                 }
+                _ => id.with("_ui"),
+            };
+            let mut ui_builder = egui::UiBuilder::new()
+                .id_salt(ui_id_salt)
+                // This does not set clipping, therefore we can still paint outside child ui
+                // (on border) and avoid initialising two child user interfaces
+                .max_rect(full_container_without_border);
+
+            ui_builder.style = egui_style;
+            ui_builder.layout = layout;
+            ui_builder.disabled = disabled;
+            if hidden {
+                // Node content is not painted and can not be interacted with
+                ui_builder = ui_builder.invisible();
             }
 
-            match overflow_style.x {
-                taffy::Overflow::Visible => {
-                    // Do nothing
-                }
-                taffy::Overflow::Clip | taffy::Overflow::Hidden | taffy::Overflow::Scroll => {
-                    // Add scroll area
-                    if overflow_style.x == taffy::Overflow::Scroll {
-                        scroll_in_directions.x = true;
-                    }
+            let mut child_ui = self.ui.new_child(ui_builder);
+            child_ui.expand_to_include_rect(full_container_without_border);
+            if opacity < 1. {
+                child_ui.multiply_opacity(opacity);
+            }
 
-                    // Hide overflow
-                    let mut clip_rect = child_ui.clip_rect();
-                    clip_rect.min.x = full_container_without_border.min.x;
-                    clip_rect.max.x = full_container_without_border.max.x;
-                    child_ui.shrink_clip_rect(clip_rect);
+            if let Some(wrap_mode) = wrap_mode {
+                if child_ui.style().wrap_mode != Some(wrap_mode) {
+                    child_ui.style_mut().wrap_mode = Some(wrap_mode);
                 }
             }
 
-            if scroll_in_directions.any() {
-                let container_width = full_container_without_border.width();
-                let container_height = full_container_without_border.height();
-                let mut scroll = egui::ScrollArea::new(scroll_in_directions)
-                    .max_height(container_height)
-                    .max_width(container_width);
+            let mut bg = background_draw.draw_dyn(&mut child_ui, &self.taffy_container);
 
-                if scroll_in_directions.x {
-                    scroll = scroll.min_scrolled_width(container_width)
+            let fg = {
+                let mut scroll_in_directions = egui::Vec2b::FALSE;
+                match overflow_style.y {
+                    taffy::Overflow::Visible => {
+                        // Do nothing
+                    }
+                    taffy::Overflow::Clip | taffy::Overflow::Hidden | taffy::Overflow::Scroll => {
+                        // Add scroll area
+                        if overflow_style.y == taffy::Overflow::Scroll {
+                            scroll_in_directions.y = true;
+                        }
+
+                        // Hide overflow
+                        let mut clip_rect = child_ui.clip_rect();
+                        clip_rect.min.y = full_container_without_border.min.y;
+                        clip_rect.max.y = full_container_without_border.max.y;
+                        child_ui.shrink_clip_rect(clip_rect);
+                    }
                 }
 
-                if scroll_in_directions.y {
-                    scroll = scroll.min_scrolled_height(container_height)
+                match overflow_style.x {
+                    taffy::Overflow::Visible => {
+                        // Do nothing
+                    }
+                    taffy::Overflow::Clip | taffy::Overflow::Hidden | taffy::Overflow::Scroll => {
+                        // Add scroll area
+                        if overflow_style.x == taffy::Overflow::Scroll {
+                            scroll_in_directions.x = true;
+                        }
+
+                        // Hide overflow
+                        let mut clip_rect = child_ui.clip_rect();
+                        clip_rect.min.x = full_container_without_border.min.x;
+                        clip_rect.max.x = full_container_without_border.max.x;
+                        child_ui.shrink_clip_rect(clip_rect);
+                    }
                 }
 
-                let resp = scroll.show_viewport(&mut child_ui, |ui, viewport_rect| {
-                    // Allocate expected size for scroll area to correctly calculate inner size
-                    let content_size = self.taffy_container.layout.content_size;
-                    let content_size = egui::Vec2::new(content_size.width, content_size.height);
-                    // TODO: Why this is needed?
-                    // Looks like taffy content size takes into account border sides
-                    //
-                    // Taffy bug or what?
-                    ui.set_min_size(
-                        (
-                            content_size
-                                - taffy_point_to_egui_pos(top_left(
-                                    &self.taffy_container.layout.border,
-                                ))
-                                .to_vec2()
-                            // * (!scroll_in_directions).to_vec2()
-                        )
-                        .max(egui::Vec2::ZERO),
-                    );
+                if scroll_in_directions.any() {
+                    let container_width = full_container_without_border.width();
+                    let container_height = full_container_without_border.height();
+                    let mut scroll = egui::ScrollArea::new(scroll_in_directions)
+                        .max_height(container_height)
+                        .max_width(container_width);
 
-                    let rect = ui.min_rect();
-                    let mut offset = -viewport_rect.min.to_vec2();
+                    if scroll_in_directions.x {
+                        scroll = scroll.min_scrolled_width(container_width)
+                    }
 
-                    let stored_viewport = self.current_viewport;
-                    let stored_viewport_content = self.current_viewport_content;
+                    if scroll_in_directions.y {
+                        scroll = scroll.min_scrolled_height(container_height)
+                    }
 
-                    self.current_viewport = self.current_rect;
-                    self.current_viewport_content = rect;
-                    std::mem::swap(&mut self.last_scroll_offset, &mut offset);
+                    if let Some(scroll_config) = &scroll_config {
+                        scroll = scroll_config.apply(scroll);
+                    }
 
-                    // We need to modify current rect to take into account nonexistent
-                    // borders when placing child elements
-                    //
-                    // taffy calculates child elements in relation to parent node
-                    // (including borders)
-                    self.current_rect = rect.translate(
-                        -taffy_point_to_egui_pos(top_left(&self.taffy_container.layout.border))
-                            .to_vec2(),
-                    );
+                    if let Some(offset) = self.requested_scroll_offset(id) {
+                        scroll = scroll.scroll_offset(offset);
+                    } else if let Some(scroll_config) = &scroll_config {
+                        let [x, y] = self.pinned_scroll_offset(id, scroll_config.stick_to_end);
+                        if let Some(x) = x {
+                            scroll = scroll.horizontal_scroll_offset(x);
+                        }
+                        if let Some(y) = y {
+                            scroll = scroll.vertical_scroll_offset(y);
+                        }
+                    }
 
-                    std::mem::swap(ui, &mut self.ui);
+                    let resp = scroll.show_viewport(&mut child_ui, |ui, viewport_rect| {
+                        // Allocate expected size for scroll area to correctly calculate inner size
+                        let content_size = self.taffy_container.layout.content_size;
+                        let content_size = egui::Vec2::new(content_size.width, content_size.height);
+                        // TODO: Why this is needed?
+                        // Looks like taffy content size takes into account border sides
+                        //
+                        // Taffy bug or what?
+                        ui.set_min_size(
+                            (
+                                content_size
+                                    - taffy_point_to_egui_pos(top_left(
+                                        &self.taffy_container.layout.border,
+                                    ))
+                                    .to_vec2()
+                                // * (!scroll_in_directions).to_vec2()
+                            )
+                            .max(egui::Vec2::ZERO),
+                        );
+
+                        let rect = ui.min_rect();
+                        let mut offset = -viewport_rect.min.to_vec2();
+                        if let Some(node_data) = self.state.id_to_node_id.get_mut(&id) {
+                            node_data.scroll_offset = viewport_rect.min.to_vec2();
+                        }
 
+                        let stored_viewport = self.current_viewport;
+                        let stored_viewport_content = self.current_viewport_content;
+                        let stored_scroll_viewport = self.current_scroll_viewport;
+
+                        self.current_viewport = self.current_rect;
+                        self.current_viewport_content = rect;
+                        self.current_scroll_viewport = viewport_rect.translate(rect.min.to_vec2());
+                        std::mem::swap(&mut self.last_scroll_offset, &mut offset);
+
+                        // We need to modify current rect to take into account nonexistent
+                        // borders when placing child elements
+                        //
+                        // taffy calculates child elements in relation to parent node
+                        // (including borders)
+                        self.current_rect = rect.translate(
+                            -taffy_point_to_egui_pos(top_left(&self.taffy_container.layout.border))
+                                .to_vec2(),
+                        );
+
+                        std::mem::swap(ui, &mut self.ui);
+
+                        let resp = f.show_dyn(self, &mut bg);
+
+                        std::mem::swap(ui, &mut self.ui);
+                        // std::mem::swap(&mut self.current_rect, &mut rect);
+                        std::mem::swap(&mut self.last_scroll_offset, &mut offset);
+                        self.current_viewport_content = stored_viewport_content;
+                        self.current_viewport = stored_viewport;
+                        self.current_scroll_viewport = stored_scroll_viewport;
+
+                        resp
+                    });
+                    if let Some(node_data) = self.state.id_to_node_id.get_mut(&id) {
+                        node_data.scroll_info = Some(ScrollInfo::new(
+                            resp.state.offset,
+                            resp.content_size,
+                            resp.inner_rect.size(),
+                        ));
+                    }
+                    resp.inner
+                } else {
+                    std::mem::swap(&mut child_ui, &mut self.ui);
                     let resp = f.show_dyn(self, &mut bg);
+                    std::mem::swap(&mut child_ui, &mut self.ui);
+                    resp
+                }
+            };
 
-                    std::mem::swap(ui, &mut self.ui);
-                    // std::mem::swap(&mut self.current_rect, &mut rect);
-                    std::mem::swap(&mut self.last_scroll_offset, &mut offset);
-                    self.current_viewport_content = stored_viewport_content;
-                    self.current_viewport = stored_viewport;
+            (fg, bg)
+        };
 
-                    resp
-                });
-                resp.inner
-            } else {
-                std::mem::swap(&mut child_ui, &mut self.ui);
-                let resp = f.show_dyn(self, &mut bg);
-                std::mem::swap(&mut child_ui, &mut self.ui);
-                resp
+        if let Some(first_shape) = first_shape {
+            self.capturing_ancestors -= 1;
+            if transition.is_some() {
+                self.store_transition_shapes(node_id, first_shape);
             }
-        };
+            if let (Some(key), Some(env), false) = (memo_key, memo_env, skipped) {
+                self.store_memo(node_id, key, env, first_shape);
+            }
+        }
+
+        // Skipped node keeps its children and their previous layout in the taffy tree
+        if !skipped {
+            self.reconcile_current_children();
+        }
+
+        if let Some(mut parent_ui) = parent_ui {
+            std::mem::swap(&mut parent_ui, &mut self.ui);
+        }
 
-        let current_cnt = self.state.taffy_tree.child_count(node_id);
-        if current_cnt > self.current_node_index {
-            self.state
-                .taffy_tree
-                .remove_children_range(node_id, self.current_node_index..current_cnt)
-                .unwrap();
+        if self.debug_overlay {
+            self.paint_debug_overlay(id, tui_id_kind, node_id);
         }
 
+        if self.state.highlighted_node == Some(id) {
+            debug::paint_box_model(&self.ui, &self.taffy_container);
+            debug::paint_hovered_content(&self.ui, &self.taffy_container);
+        }
+
+        let mut children = std::mem::replace(&mut self.current_children, stored_children);
+        children.clear();
+        self.children_buffers.push(children);
+
         self.current_id = stored_id;
         self.current_node = stored_node;
         self.current_node_index = stored_current_node_index;
@@ -542,6 +1099,546 @@ impl Tui {
         }
     }
 
+    /// Update enter transition state of the node and restrict node style during transition.
+    ///
+    /// Returns node content opacity.
+    fn update_transition(
+        &mut self,
+        id: egui::Id,
+        transition: Option<NodeTransition>,
+        style: &mut taffy::Style,
+    ) -> f32 {
+        let Some(node_id) = self.state.id_to_node_id.get(&id).map(|data| data.node_id) else {
+            // New nodes are handled after they are created
+            return 1.;
+        };
+
+        let Some(transition) = transition else {
+            self.state.transitions.remove(&node_id);
+            return 1.;
+        };
+
+        let time = self.ui.input(|i| i.time);
+        let layout_size = self.state.layout(node_id).size;
+
+        let state =
+            self.state.transitions.entry(node_id).or_insert_with(|| {
+                TransitionState::new(id, transition, TransitionPhase::Shown, time)
+            });
+        state.config = transition;
+
+        let visibility =
+            state.update_shown(time, egui::vec2(layout_size.width, layout_size.height));
+        if visibility < 1. {
+            state.apply_collapse(style, visibility);
+            self.ui.ctx().request_repaint();
+        }
+        state.opacity(visibility)
+    }
+
+    /// Should node content be skipped. Memoized node content is replayed.
+    fn skip_content(
+        &mut self,
+        node_id: NodeId,
+        condition: ContentSkip,
+        memo_env: Option<&MemoEnv>,
+    ) -> bool {
+        if self.taffy_container.first_frame {
+            return false;
+        }
+
+        let rect = self.taffy_container.full_container();
+        match condition {
+            // Node was outside of the visible area in the last layout
+            ContentSkip::Offscreen => !self.ui.clip_rect().intersects(rect),
+            // Checked by the caller
+            ContentSkip::DisplayNone => false,
+            ContentSkip::Memo(key) => match (self.state.memos.get(&node_id), memo_env) {
+                (Some(memo), Some(env)) if memo.matches(key, env, rect, self.ui.clip_rect()) => {
+                    memo.paint(&self.ui, rect);
+                    true
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Record shapes painted by memoized node starting from given shape index
+    fn store_memo(&mut self, node_id: NodeId, key: u64, env: MemoEnv, first_shape: usize) {
+        if self.taffy_container.first_frame || self.measure_phase {
+            // Content is not painted
+            self.state.memos.remove(&node_id);
+            return;
+        }
+
+        let shapes = self.painted_shapes(first_shape);
+        let rect = self.taffy_container.full_container();
+        let clip_rect = self.ui.clip_rect();
+        let memo = MemoState::new(key, env, rect, clip_rect, self.state.generation, shapes);
+        self.state.memos.insert(node_id, memo);
+    }
+
+    /// Move sticky node into the visible area of its nearest scrollable ancestor
+    /// (See [`TuiBuilderLogic::position_sticky`])
+    fn apply_position_sticky(
+        &mut self,
+        id: egui::Id,
+        inset: taffy::Rect<LengthPercentageAuto>,
+        container: &mut TaffyContainerUi,
+    ) {
+        if container.first_frame {
+            // Node position is not known yet
+            return;
+        }
+
+        let rect = container.full_container();
+        let viewport = self.current_scroll_viewport;
+        // Sticky node stays inside of its parent content box
+        let bounds = self.current_node.map(|parent| {
+            let overflow = self.state.taffy_tree.style(parent).unwrap().overflow;
+            if overflow.x == taffy::Overflow::Scroll || overflow.y == taffy::Overflow::Scroll {
+                // Scrolled content of the scrollable parent
+                self.current_viewport_content
+            } else {
+                self.taffy_container
+                    .full_container_without_border_and_padding()
+            }
+        });
+
+        let delta = egui::vec2(
+            scroll::sticky_axis_offset(
+                rect.x_range(),
+                viewport.x_range(),
+                inset.left.resolve_to_option(viewport.width()),
+                inset.right.resolve_to_option(viewport.width()),
+                bounds.map(|bounds| bounds.x_range()),
+            ),
+            scroll::sticky_axis_offset(
+                rect.y_range(),
+                viewport.y_range(),
+                inset.top.resolve_to_option(viewport.height()),
+                inset.bottom.resolve_to_option(viewport.height()),
+                bounds.map(|bounds| bounds.y_range()),
+            ),
+        );
+        container.sticky_delta = delta;
+
+        if let Some(node_data) = self.state.id_to_node_id.get_mut(&id) {
+            node_data.stuck = container.stuck();
+        }
+    }
+
+    /// Child ui of the stuck node in the sticky layer that is placed directly above the
+    /// Tui layer, so stuck node is painted and interacted with above the following content.
+    /// Returns `None` if the current ui is already in the sticky layer.
+    fn sticky_ui(&mut self, id: egui::Id) -> Option<egui::Ui> {
+        let layer_id = self.ui.layer_id();
+        let sticky_layer_id =
+            egui::LayerId::new(layer_id.order, self.main_id.with("egui_taffy_sticky"));
+        if layer_id == sticky_layer_id {
+            // Nested stuck node
+            return None;
+        }
+
+        let ctx = self.ui.ctx();
+        // egui supports only one level of sublayers
+        let parent_layer_id = ctx
+            .memory(|memory| memory.areas().parent_layer(layer_id))
+            .unwrap_or(layer_id);
+        ctx.set_sublayer(parent_layer_id, sticky_layer_id);
+        if let Some(transform) = ctx.layer_transform_to_global(layer_id) {
+            ctx.set_transform_layer(sticky_layer_id, transform);
+        }
+
+        Some(
+            self.ui.new_child(
+                UiBuilder::new()
+                    .id_salt(id.with("_sticky"))
+                    .layer_id(sticky_layer_id)
+                    .max_rect(self.ui.max_rect()),
+            ),
+        )
+    }
+
+    /// Scroll current node into view if it was requested (See [`Tui::scroll_to`])
+    fn handle_scroll_request(&mut self, id: egui::Id) {
+        if self.taffy_container.first_frame || self.measure_phase {
+            // Node position is not known yet
+            return;
+        }
+        let Some(request) = self.state.scroll_requests.get(&id) else {
+            return;
+        };
+        if request.scroll_to_node(&self.ui, self.taffy_container.full_container()) {
+            self.state.scroll_requests.remove(&id);
+        }
+    }
+
+    /// Take requested scroll offset of the current scrollable node
+    /// (See [`Tui::set_scroll_offset`])
+    fn requested_scroll_offset(&mut self, id: egui::Id) -> Option<egui::Vec2> {
+        if self.taffy_container.first_frame || self.measure_phase {
+            // Node content size is not known yet
+            return None;
+        }
+        let offset = self.state.scroll_requests.get(&id)?.scroll_offset()?;
+        self.state.scroll_requests.remove(&id);
+        Some(match self.max_scroll_offset(id) {
+            Some(max_offset) => offset.clamp(egui::Vec2::ZERO, max_offset),
+            None => offset,
+        })
+    }
+
+    /// Scroll offsets that keep pinned viewport at the end of current node content
+    /// (See [`ScrollConfig::stick_to_bottom`])
+    fn pinned_scroll_offset(&self, id: egui::Id, stick_to_end: egui::Vec2b) -> [Option<f32>; 2] {
+        if !stick_to_end.any() || self.taffy_container.first_frame || self.measure_phase {
+            return [None; 2];
+        }
+        let Some(max_offset) = self.max_scroll_offset(id) else {
+            return [None; 2];
+        };
+        let stuck_to_end = self.state.id_to_node_id[&id]
+            .scroll_info
+            .map_or(egui::Vec2b::FALSE, |info| info.stuck_to_end);
+
+        [0, 1].map(|d| (stick_to_end[d] && stuck_to_end[d]).then_some(max_offset[d]))
+    }
+
+    /// Maximal scroll offset of the current scrollable node in this pass.
+    ///
+    /// Content size is not yet known to egui scroll area if layout was recalculated,
+    /// therefore the same content size that is allocated in the scroll area is used.
+    fn max_scroll_offset(&self, id: egui::Id) -> Option<egui::Vec2> {
+        // Viewport size is not known before scroll area is shown
+        let info = self.state.id_to_node_id.get(&id)?.scroll_info?;
+
+        let layout = &self.taffy_container.layout;
+        let content_size = (egui::vec2(layout.content_size.width, layout.content_size.height)
+            - taffy_point_to_egui_pos(top_left(&layout.border)).to_vec2())
+        .max(egui::Vec2::ZERO);
+        Some((content_size - info.viewport_size).max(egui::Vec2::ZERO))
+    }
+
+    /// Scroll requested descendants of the skipped node into view using their last layout
+    fn handle_skipped_scroll_requests(&mut self, node_id: NodeId) {
+        if self.measure_phase {
+            return;
+        }
+
+        let origin = self.taffy_container.full_container().min;
+        let state = &mut *self.state;
+        let ui = &self.ui;
+        state.scroll_requests.retain(|id, request| {
+            let Some(mut current) = state.id_to_node_id.get(id).map(|data| data.node_id) else {
+                return true;
+            };
+            let size = state.taffy_tree.layout(current).unwrap().size;
+
+            // Descendant position relative to the skipped node
+            let mut offset = egui::Vec2::ZERO;
+            while current != node_id {
+                let location = state.taffy_tree.layout(current).unwrap().location;
+                offset += egui::vec2(location.x, location.y);
+                match state.taffy_tree.parent(current) {
+                    Some(parent) => current = parent,
+                    // Not a descendant
+                    None => return true,
+                }
+            }
+
+            let rect =
+                egui::Rect::from_min_size(origin + offset, egui::vec2(size.width, size.height));
+            !request.scroll_to_node(ui, rect)
+        });
+    }
+
+    /// Remove transition state of the node that does not use transitions anymore
+    fn remove_transition(&mut self, id: egui::Id) {
+        if self.state.transitions.is_empty() {
+            return;
+        }
+        if let Some(node_data) = self.state.id_to_node_id.get(&id) {
+            let node_id = node_data.node_id;
+            self.state.transitions.remove(&node_id);
+        }
+    }
+
+    /// Store shapes painted by node starting from given shape index for exit transition
+    fn store_transition_shapes(&mut self, node_id: NodeId, first_shape: usize) {
+        let shapes = self.painted_shapes(first_shape);
+
+        let rect = self.taffy_container.full_container();
+        if let Some(state) = self.state.transitions.get_mut(&node_id) {
+            state.store_shapes(shapes, rect);
+        }
+    }
+
+    /// Keep removed children with exit transition in their last layout slot
+    fn keep_exiting_children(&mut self, parent: NodeId) {
+        let time = self.ui.input(|i| i.time);
+        let state = self.state.deref_mut();
+
+        let previous = state.taffy_tree.children(parent).unwrap();
+        if !previous
+            .iter()
+            .any(|child| state.transitions.contains_key(child))
+        {
+            return;
+        }
+
+        let current_idx: HashMap<NodeId, usize> = self
+            .current_children
+            .iter()
+            .enumerate()
+            .map(|(idx, child)| (*child, idx))
+            .collect();
+
+        let mut exiting = Vec::new();
+        let mut insert_idx = 0;
+        // Shapes inserted before later siblings shift their recorded shape indices
+        let mut shift = 0;
+        for child in previous {
+            if let Some(idx) = current_idx.get(&child) {
+                insert_idx = idx + 1;
+                continue;
+            }
+
+            let Some(transition) = state.transitions.get_mut(&child) else {
+                continue;
+            };
+            let Some(visibility) = transition.update_hidden(time) else {
+                // Exit transition finished, node is removed
+                continue;
+            };
+
+            if let Some(node_data) = state.id_to_node_id.get_mut(&transition.id) {
+                node_data.generation = state.generation;
+                // Stored style is modified below
+                if let Some(style) = node_data.style.take() {
+                    state.styles.release(style);
+                }
+            }
+
+            let mut style = state.taffy_tree.style(child).unwrap().clone();
+            transition.apply_exit_style(&mut style, visibility);
+            if state.taffy_tree.style(child).unwrap() != &style {
+                state.taffy_tree.set_style(child, style).unwrap();
+            }
+
+            let layout = state.taffy_tree.layout(child).unwrap();
+            let rect = egui::Rect::from_min_size(
+                self.current_rect.min + egui::vec2(layout.location.x, layout.location.y),
+                egui::vec2(layout.size.width, layout.size.height),
+            );
+
+            let first_shape = layer_next_shape_idx(&self.ui);
+            transition.paint_exit(&self.ui, rect, visibility);
+            // Paint exiting child below the following shown sibling, at its previous paint order
+            let next_sibling_shape = self
+                .current_children
+                .get(insert_idx)
+                .and_then(|next| self.child_first_shapes.get(next));
+            if let Some(next_sibling_shape) = next_sibling_shape {
+                shift += move_painted_shapes(&self.ui, first_shape, next_sibling_shape + shift);
+            }
+
+            exiting.push((insert_idx, child));
+            self.ui.ctx().request_repaint();
+        }
+
+        // Inserted in reverse order so that insertion indices stay valid
+        for (idx, child) in exiting.into_iter().rev() {
+            self.current_children.insert(idx, child);
+        }
+    }
+
+    /// Index of the next shape that will be painted in the Tui layer
+    fn next_shape_idx(&self) -> usize {
+        layer_next_shape_idx(&self.ui)
+    }
+
+    /// Shapes painted in the Tui layer starting from given shape index
+    fn painted_shapes(&self, first_shape: usize) -> Vec<egui::epaint::ClippedShape> {
+        let mut shapes = Vec::new();
+        self.copy_painted_shapes(first_shape, &mut shapes);
+        shapes
+    }
+
+    /// Replace `shapes` with shapes painted in the Tui layer starting from given shape index.
+    /// Allocation of `shapes` is reused.
+    fn copy_painted_shapes(
+        &self,
+        first_shape: usize,
+        shapes: &mut Vec<egui::epaint::ClippedShape>,
+    ) {
+        shapes.clear();
+        self.ui.ctx().graphics(|graphics| {
+            let Some(paint_list) = graphics.get(self.ui.layer_id()) else {
+                return;
+            };
+            let count = paint_list.next_idx().0.saturating_sub(first_shape);
+            shapes.extend(
+                paint_list
+                    .all_entries()
+                    .skip(first_shape)
+                    .take(count)
+                    .cloned(),
+            );
+        });
+    }
+
+    /// Store painted shapes of settled layout or replace shapes of unsettled layout
+    /// with the last settled layout shapes (See [`TuiInitializer::double_buffer`])
+    fn double_buffer_paint(&mut self, first_shape: usize) {
+        if self.measure_phase {
+            return;
+        }
+        if !self.double_buffer {
+            self.state.settled_paint = None;
+            return;
+        }
+
+        let ctx = self.ui.ctx().clone();
+        let layer_id = self.ui.layer_id();
+        let root_rect = self.root_rect;
+
+        let settled = self.state.report.converged && !self.added_nodes;
+        if settled {
+            // Reuse shape allocation of the previous settled paint
+            let mut shapes = self
+                .state
+                .settled_paint
+                .take()
+                .map(|settled_paint| settled_paint.shapes)
+                .unwrap_or_default();
+            self.copy_painted_shapes(first_shape, &mut shapes);
+            self.state.settled_paint = Some(SettledPaint {
+                shapes,
+                rect: root_rect,
+                stale_since_frame: None,
+            });
+            return;
+        }
+
+        let Some(settled_paint) = &mut self.state.settled_paint else {
+            return;
+        };
+
+        let frame_nr = ctx.cumulative_frame_nr();
+        let stale_since_frame = *settled_paint.stale_since_frame.get_or_insert(frame_nr);
+        if frame_nr - stale_since_frame > MAX_STALE_FRAMES {
+            // Layout does not settle, show current layout instead
+            return;
+        }
+
+        let delta = root_rect.min - settled_paint.rect.min;
+        ctx.graphics_mut(|graphics| {
+            let paint_list = graphics.entry(layer_id);
+            for idx in first_shape..paint_list.next_idx().0 {
+                paint_list.reset_shape(egui::layers::ShapeIdx(idx));
+            }
+            for clipped_shape in &settled_paint.shapes {
+                let mut shape = clipped_shape.shape.clone();
+                shape.translate(delta);
+                paint_list.add(clipped_shape.clip_rect.translate(delta), shape);
+            }
+        });
+
+        // Current widgets are not where stale content is painted, block interaction with them.
+        // Widget registered last is above the widgets of the current layout.
+        let blocked_rect = root_rect.union(settled_paint.rect.translate(delta));
+        self.ui.interact(
+            blocked_rect,
+            self.main_id.with("egui_taffy_stale_paint_blocker"),
+            egui::Sense::click_and_drag(),
+        );
+    }
+
+    /// Replace node location and size with animated values if layout animation is enabled
+    fn animate_layout(
+        &mut self,
+        id: egui::Id,
+        animation: Option<LayoutAnimation>,
+        layout: &mut Layout,
+    ) {
+        let Some(node_data) = self.state.id_to_node_id.get_mut(&id) else {
+            return;
+        };
+
+        let animation = animation.filter(|_| self.ui.style().animation_time > 0.);
+        let Some(animation) = animation else {
+            node_data.animation = None;
+            return;
+        };
+
+        let target = egui::Rect::from_min_size(
+            egui::pos2(layout.location.x, layout.location.y),
+            egui::vec2(layout.size.width, layout.size.height),
+        );
+        if target.any_nan() {
+            return;
+        }
+
+        let time = self.ui.input(|i| i.time);
+        let (rect, animating) = node_data
+            .animation
+            .get_or_insert_with(|| NodeAnimation::new(target, time))
+            .update(target, time, &animation);
+
+        if animating {
+            layout.location = taffy::Point {
+                x: rect.min.x,
+                y: rect.min.y,
+            };
+            layout.size = Size {
+                width: rect.width(),
+                height: rect.height(),
+            };
+            self.ui.ctx().request_repaint();
+        }
+    }
+
+    /// Paint box model of current node and show tooltip if node is hovered
+    fn paint_debug_overlay(&mut self, id: egui::Id, tui_id_kind: &'static str, node_id: NodeId) {
+        // Children are painted first, first hovered node is the deepest one
+        let hovered = debug::paint_box_model(&self.ui, &self.taffy_container);
+
+        if hovered && !self.debug_overlay_hover_shown {
+            self.debug_overlay_hover_shown = true;
+            debug::paint_hovered_content(&self.ui, &self.taffy_container);
+
+            let taffy_tree = &self.state.taffy_tree;
+            debug::show_node_tooltip(
+                &self.ui,
+                &self.taffy_container,
+                debug::DebugNodeInfo {
+                    id,
+                    tui_id_kind,
+                    display: taffy_tree.style(node_id).unwrap().display,
+                    context: taffy_tree.get_node_context(node_id),
+                },
+            );
+        }
+    }
+
+    /// Update current node children in taffy tree to match children placed during this frame
+    fn reconcile_current_children(&mut self) {
+        let Some(current_node) = self.current_node else {
+            return;
+        };
+        if !self.state.transitions.is_empty() {
+            self.keep_exiting_children(current_node);
+        }
+        let state = self.state.deref_mut();
+        reconcile::reconcile_children(
+            &mut state.taffy_tree,
+            current_node,
+            &self.current_children,
+            &mut state.detached_nodes,
+        );
+    }
+
     #[inline]
     fn add_container<T>(
         &mut self,
@@ -555,9 +1652,12 @@ impl Tui {
     /// Add egui user interface as child node in the Tui
     fn add_container_dyn<T>(
         &mut self,
-        params: TuiBuilderParams,
+        mut params: TuiBuilderParams,
         content: StackBoxDynFnOnceEguiUiContainer<T>,
     ) -> T {
+        // Leaf content is placed in node child ui
+        params.layout_only = false;
+
         let fg_bg = self.add_child(params, (), |tui, _| {
             let taffy_container = &tui.taffy_container;
 
@@ -581,12 +1681,24 @@ impl Tui {
             let mut max_size = resp.max_size;
             max_size = max_size.max(min_size);
 
+            let text_layout = resp.text_layout.map(|mut text_layout| {
+                // Wrapping width is provided by the layout algorithm
+                if text_layout.wrap.max_width.is_finite() && text_layout.wrap.max_width != 0. {
+                    Arc::make_mut(&mut text_layout).wrap.max_width = 0.;
+                }
+                text_layout
+            });
+
             let new_content = Context {
                 min_size,
                 max_size,
                 infinite: resp.infinite,
+                text_layout,
+                measure: resp.measure,
             };
             if tui.state.taffy_tree.get_node_context(nodeid) != Some(&new_content) {
+                let (pass, id) = (tui.pass_index, tui.current_id);
+                tui.state.report.record(pass, id, NodeChangeKind::Context);
                 tui.state
                     .taffy_tree
                     .set_node_context(nodeid, Some(new_content))
@@ -604,120 +1716,157 @@ impl Tui {
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn recalculate(&mut self) -> TaffyContainerUi {
         let root_rect = self.root_rect;
-        let available_space = self.available_space.unwrap_or(Size {
-            width: AvailableSpace::Definite(root_rect.width()),
-            height: AvailableSpace::Definite(root_rect.height()),
-        });
-
         let current_node = self.current_node.unwrap();
 
+        // Root node children must be up to date before layout calculation
+        self.reconcile_current_children();
+
         // Remove all unused nodes
         let state = self.state.deref_mut();
-        state.id_to_node_id.retain(|_k, node_data| {
-            if node_data.keep {
-                node_data.keep = false;
-                return true;
+        state.collect_garbage();
+
+        if let Some(previous_root) = state.root_node.filter(|root| *root != current_node) {
+            // Root node changed, previous root is not attached to any node
+            state.detached_nodes.push(previous_root);
+            state.collect_garbage();
+        }
+        state.root_node = Some(current_node);
+
+        if state.taffy_tree.dirty(current_node).unwrap() || state.last_size != root_rect.size() {
+            self.compute_layout(current_node);
+            self.layout_changed = true;
+
+            // Content recorded in this pass may be laid out differently
+            let state = self.state.deref_mut();
+            let generation = state.generation;
+            state.memos.retain(|_, memo| memo.generation != generation);
+
+            #[cfg(feature = "serde")]
+            {
+                state.layout_cache_outdated = true;
             }
 
-            // Remove unused node
-            let node_id = node_data.node_id;
-            if let Some(parent) = state.taffy_tree.parent(node_id) {
-                state.taffy_tree.remove_child(parent, node_id).unwrap();
+            state.report.recalculations += 1;
+            if self.measure_phase {
+                // Layout is used by the following paint phase in the same pass
+                return self.root_container(current_node);
+            }
+
+            let ctx = self.ui.ctx();
+            let state = self.state.deref_mut();
+            state.report.converged = false;
+
+            let policy = self.convergence;
+            let max_passes = ctx.options(|options| options.max_passes.get());
+            let can_discard = policy.strategy == ConvergenceStrategy::Discard
+                && self.pass_index < policy.max_extra_passes
+                && self.pass_index + 1 < max_passes;
+
+            if can_discard {
+                ctx.request_discard("Taffy recalculation");
+            } else {
+                // Layout will be applied in the next frame
+                state.report.deferred = true;
+                if policy.strategy == ConvergenceStrategy::Discard {
+                    log::debug!(
+                        "Taffy layout did not converge in {} passes, changes: {:?}",
+                        state.report.passes,
+                        state.report.changes
+                    );
+                }
+                ctx.request_repaint();
+            }
+        } else {
+            #[cfg(feature = "serde")]
+            if self.persist_layout && !self.measure_phase && state.layout_cache_outdated {
+                // Layout is stored once it settles instead of after every recalculation
+                state.layout_cache_outdated = false;
+                self.store_layout_cache();
             }
-            state.taffy_tree.remove(node_id).unwrap();
-            false
+        }
+
+        self.root_container(current_node)
+    }
+
+    /// Compute taffy layout of the tree with given root node
+    fn compute_layout(&mut self, root_node: NodeId) {
+        let root_rect = self.root_rect;
+        let available_space = self.available_space.unwrap_or(Size {
+            width: AvailableSpace::Definite(root_rect.width()),
+            height: AvailableSpace::Definite(root_rect.height()),
         });
 
+        let ctx = self.ui.ctx();
+        let state = self.state.deref_mut();
         let taffy = &mut state.taffy_tree;
 
-        if taffy.dirty(current_node).unwrap() || state.last_size != root_rect.size() {
-            // let ctx = self.ui.ctx();
-
-            state.last_size = root_rect.size();
-            taffy
-                .compute_layout_with_measure(
-                    current_node,
-                    available_space,
-                    |_known_size: Size<Option<f32>>,
-                     available_space: Size<AvailableSpace>,
-                     _id,
-                     context,
-                     _style|
-                     -> Size<f32> {
-                        let context = context.copied().unwrap_or(Context {
-                            min_size: egui::Vec2::ZERO,
-                            max_size: egui::Vec2::ZERO,
-                            infinite: egui::Vec2b::FALSE,
-                        });
-
-                        let Context {
-                            mut min_size,
-                            mut max_size,
-                            infinite,
-                        } = context;
-
-                        // if scroll_area {
-                        //     min_size = egui::Vec2::ZERO;
-                        // }
-
-                        if min_size.any_nan() {
-                            min_size = egui::Vec2::ZERO;
-                        }
-                        if max_size.any_nan() {
-                            max_size = root_rect.size();
-                        }
+        state.last_size = root_rect.size();
+        taffy
+            .compute_layout_with_measure(
+                root_node,
+                available_space,
+                |known_size: Size<Option<f32>>,
+                 available_space: Size<AvailableSpace>,
+                 _id,
+                 context,
+                 _style|
+                 -> Size<f32> {
+                    let Some(context) = context else {
+                        return Size::ZERO;
+                    };
+
+                    #[allow(clippy::let_and_return)]
+                    let final_size =
+                        context.measure(known_size, available_space, root_rect.size(), ctx);
+
+                    // println!(
+                    //     "{:?} {:?} {:?} {:?}",
+                    //     _id, available_space, final_size, known_size,
+                    // );
+
+                    final_size
+                },
+            )
+            .unwrap();
+        // taffy.print_tree(current_node);
+
+        log::trace!("Taffy recalculation done!");
+    }
 
-                        let max_size = egui::Vec2 {
-                            x: if infinite.x {
-                                root_rect.width()
-                            } else {
-                                max_size.x
-                            },
-                            y: if infinite.y {
-                                root_rect.height()
-                            } else {
-                                max_size.y
-                            },
-                        };
-
-                        let width = match available_space.width {
-                            AvailableSpace::Definite(num) => {
-                                num.clamp(min_size.x, max_size.x.max(min_size.x))
-                            }
-                            AvailableSpace::MinContent => min_size.x,
-                            AvailableSpace::MaxContent => max_size.x,
-                        };
-                        let height = match available_space.height {
-                            AvailableSpace::Definite(num) => {
-                                num.clamp(min_size.y, max_size.y.max(min_size.y))
-                            }
-                            AvailableSpace::MinContent => min_size.y,
-                            AvailableSpace::MaxContent => max_size.y,
-                        };
-
-                        #[allow(clippy::let_and_return)]
-                        let final_size = Size { width, height };
-
-                        // println!(
-                        //     "{:?} {:?} {:?} {:?} {:?} {:?}",
-                        //     _id, min_size, max_size, available_space, final_size, _known_size,
-                        // );
-
-                        final_size
-                    },
-                )
-                .unwrap();
-            // taffy.print_tree(current_node);
+    /// Store measured layout in egui persisted memory (See [`TuiInitializer::persist_layout`])
+    #[cfg(feature = "serde")]
+    fn store_layout_cache(&mut self) {
+        let cache = persistence::LayoutCache::from_state(&self.state);
+        self.ui.data_mut(|data| {
+            data.insert_persisted(persistence::LayoutCache::id(self.main_id), cache)
+        });
+    }
+
+    /// Restore measured layout from egui persisted memory and compute it for the current
+    /// root rect, so nodes are shown laid out in the first frame.
+    #[cfg(feature = "serde")]
+    fn restore_layout_cache(&mut self) {
+        let cache_id = persistence::LayoutCache::id(self.main_id);
+        let Some(cache) = self
+            .ui
+            .data_mut(|data| data.get_persisted::<persistence::LayoutCache>(cache_id))
+        else {
+            return;
+        };
 
-            log::trace!("Taffy recalculation done!");
-            self.ui.ctx().request_discard("Taffy recalculation");
+        if let Some(root_node) = cache.restore(&mut self.state) {
+            self.compute_layout(root_node);
         }
+    }
 
+    /// Root node container information
+    fn root_container(&self, root_node: NodeId) -> TaffyContainerUi {
         TaffyContainerUi {
-            parent_rect: root_rect,
-            layout: *self.state.layout(current_node),
+            parent_rect: self.root_rect,
+            layout: *self.state.layout(root_node),
             first_frame: false,
             sticky: egui::Vec2b::FALSE,
+            sticky_delta: egui::Vec2::ZERO,
             last_scroll_offset: egui::Vec2::ZERO,
         }
     }
@@ -838,6 +1987,119 @@ impl Tui {
         &self.state
     }
 
+    /// Enable or disable layout debug overlay for nodes that are added after this call
+    ///
+    /// Overlay paints margin, border, padding and content rectangles of every node
+    /// and shows information about hovered node.
+    #[inline]
+    pub fn set_debug_overlay(&mut self, enabled: bool) {
+        self.debug_overlay = enabled;
+    }
+
+    /// Is layout debug overlay enabled
+    #[inline]
+    pub fn debug_overlay(&self) -> bool {
+        self.debug_overlay
+    }
+
+    /// Mark layout of every node as dirty, layout is fully recalculated in this pass
+    /// (See [`TaffyState::invalidate`])
+    pub fn invalidate(&mut self) {
+        self.state.invalidate();
+    }
+
+    /// Drop all cached layout state at the end of this pass, Tui is laid out
+    /// from scratch in the next pass (See [`TaffyState::reset`])
+    pub fn reset_state(&mut self) {
+        self.reset_state = true;
+    }
+
+    /// Scroll node into view in its nearest scrollable ancestor (`overflow: Scroll` node
+    /// or any enclosing [`egui::ScrollArea`]).
+    ///
+    /// Node id is resolved relative to the current node, as if the node was added here.
+    /// Node is scrolled when it is shown, therefore it can be added later in this pass.
+    /// Nodes which content is skipped (See [`TuiBuilderLogic::add_culled`]) are scrolled to
+    /// using their last layout. Use [`TaffyState::scroll_to`] outside of the layout closure.
+    ///
+    /// `align` works the same as in [`egui::Ui::scroll_to_rect`].
+    pub fn scroll_to(&mut self, id: impl Into<TuiId>, align: Option<egui::Align>) {
+        let id = id.into().resolve(self);
+        self.state.scroll_to(id, align);
+    }
+
+    /// Same as [`Tui::scroll_to`], but allows to specify scroll animation
+    pub fn scroll_to_animation(
+        &mut self,
+        id: impl Into<TuiId>,
+        align: Option<egui::Align>,
+        animation: egui::style::ScrollAnimation,
+    ) {
+        let id = id.into().resolve(self);
+        self.state.scroll_to_animation(id, align, animation);
+    }
+
+    /// Scroll state of scrollable node (`overflow: Scroll`).
+    ///
+    /// Node id is resolved relative to the current node. Returns state of the current pass
+    /// if node was already shown in this pass, otherwise state of the last frame.
+    pub fn scroll_info(&self, id: impl Into<TuiId>) -> Option<ScrollInfo> {
+        let id = id.into().resolve(self);
+        self.state.scroll_info(id)
+    }
+
+    /// Set scroll offset of scrollable node (`overflow: Scroll`), for example,
+    /// to restore stored scroll position.
+    ///
+    /// Node id is resolved relative to the current node. Offset is applied when node
+    /// is shown with known layout (Not in the first frame of the node). Offset is clamped
+    /// to node content size. Use [`TaffyState::set_scroll_offset`] outside of the layout closure.
+    pub fn set_scroll_offset(&mut self, id: impl Into<TuiId>, offset: egui::Vec2) {
+        let id = id.into().resolve(self);
+        self.state.set_scroll_offset(id, offset);
+    }
+
+    /// Is sticky node (See [`TuiBuilderLogic::position_sticky`]) moved from its normal position
+    /// in specified dimensions.
+    ///
+    /// Node id is resolved relative to the current node. Returns state of the current pass
+    /// if node was already shown in this pass, otherwise state of the last frame.
+    pub fn stuck(&self, id: impl Into<TuiId>) -> egui::Vec2b {
+        let id = id.into().resolve(self);
+        self.state
+            .id_to_node_id
+            .get(&id)
+            .map_or(egui::Vec2b::FALSE, |node_data| node_data.stuck)
+    }
+
+    /// Enable or disable double buffered painting (See [`TuiInitializer::double_buffer`])
+    #[inline]
+    pub fn set_double_buffer(&mut self, enabled: bool) {
+        self.double_buffer = enabled;
+    }
+
+    /// Enable or disable storing of measured layout (See [`TuiInitializer::persist_layout`]).
+    ///
+    /// Stored layout is restored before the layout closure is called, therefore
+    /// only [`TuiInitializer::persist_layout`] enables layout restoration.
+    #[cfg(feature = "serde")]
+    #[inline]
+    pub fn set_persist_layout(&mut self, enabled: bool) {
+        self.persist_layout = enabled;
+    }
+
+    /// Set layout convergence policy (See [`TuiInitializer::convergence`])
+    #[inline]
+    pub fn set_convergence_policy(&mut self, policy: ConvergencePolicy) {
+        self.convergence = policy;
+    }
+
+    /// Layout convergence policy
+    #[inline]
+    pub fn convergence_policy(&self) -> ConvergencePolicy {
+        self.convergence
+    }
+
     /// Retrieve taffy id that was used to identify this egui_taffy instance in egui data
     #[inline]
     pub fn main_taffy_id(&self) -> egui::Id {
@@ -855,14 +2117,236 @@ pub struct TaffyReturn<T> {
     pub container: TaffyContainerUi,
 }
 
-/// Sizing context retrieved from Tui layout leaf nodes (egui widgets or child egui::Ui)
-///
-/// Used to calculate final layout in taffy layout calculations
-#[derive(PartialEq, Default, Clone, Copy)]
-pub struct Context {
-    min_size: egui::Vec2,
-    max_size: egui::Vec2,
-    infinite: egui::Vec2b,
+/// Sizing context retrieved from Tui layout leaf nodes (egui widgets or child egui::Ui)
+///
+/// Used to calculate final layout in taffy layout calculations
+#[derive(PartialEq, Default, Clone)]
+pub struct Context {
+    min_size: egui::Vec2,
+    max_size: egui::Vec2,
+    infinite: egui::Vec2b,
+    text_layout: Option<Arc<egui::text::LayoutJob>>,
+    measure: Option<BoxedTuiMeasure>,
+}
+
+impl Context {
+    /// Minimal size that leaf node can shrink to
+    #[inline]
+    pub fn min_size(&self) -> egui::Vec2 {
+        self.min_size
+    }
+
+    /// Maximal size that leaf node can grow to
+    #[inline]
+    pub fn max_size(&self) -> egui::Vec2 {
+        self.max_size
+    }
+
+    /// Can leaf node grow to infinite size in given dimensions
+    #[inline]
+    pub fn infinite(&self) -> egui::Vec2b {
+        self.infinite
+    }
+
+    /// Text layout used to measure leaf node
+    #[inline]
+    pub fn text_layout(&self) -> Option<&Arc<egui::text::LayoutJob>> {
+        self.text_layout.as_ref()
+    }
+
+    /// Is leaf node measured by custom [`TuiMeasure`] implementation
+    #[inline]
+    pub fn has_custom_measure(&self) -> bool {
+        self.measure.is_some()
+    }
+
+    /// Calculate leaf node size for given taffy layout constraints
+    fn measure(
+        &self,
+        known_size: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+        max_available_size: egui::Vec2,
+        ctx: &egui::Context,
+    ) -> Size<f32> {
+        if let Some(measure) = &self.measure {
+            // Fonts are not locked, custom measure can lay out text
+            return measure.0.measure_dyn(known_size, available_space);
+        }
+
+        if let Some(text_layout) = &self.text_layout {
+            return ctx
+                .fonts(|fonts| measure_text(text_layout, known_size, available_space, fonts));
+        }
+
+        let mut min_size = self.min_size;
+        let mut max_size = self.max_size;
+        let infinite = self.infinite;
+
+        if min_size.any_nan() {
+            min_size = egui::Vec2::ZERO;
+        }
+        if max_size.any_nan() {
+            max_size = max_available_size;
+        }
+
+        let max_size = egui::Vec2 {
+            x: if infinite.x {
+                max_available_size.x
+            } else {
+                max_size.x
+            },
+            y: if infinite.y {
+                max_available_size.y
+            } else {
+                max_size.y
+            },
+        };
+
+        let width = match available_space.width {
+            AvailableSpace::Definite(num) => num.clamp(min_size.x, max_size.x.max(min_size.x)),
+            AvailableSpace::MinContent => min_size.x,
+            AvailableSpace::MaxContent => max_size.x,
+        };
+        let height = match available_space.height {
+            AvailableSpace::Definite(num) => num.clamp(min_size.y, max_size.y.max(min_size.y)),
+            AvailableSpace::MinContent => min_size.y,
+            AvailableSpace::MaxContent => max_size.y,
+        };
+
+        Size { width, height }
+    }
+}
+
+/// Lay out text at the width requested by taffy and return the size of the wrapped text
+fn measure_text(
+    text_layout: &egui::text::LayoutJob,
+    known_size: Size<Option<f32>>,
+    available_space: Size<AvailableSpace>,
+    fonts: &egui::text::Fonts,
+) -> Size<f32> {
+    // Words are not broken unless text can be broken anywhere
+    let wrap_words = text_layout.wrap.max_width.is_finite() && !text_layout.wrap.break_anywhere;
+    let width = known_size.width.unwrap_or(match available_space.width {
+        AvailableSpace::Definite(width) if wrap_words => {
+            width.max(text_min_content_width(text_layout, fonts))
+        }
+        AvailableSpace::Definite(width) => width,
+        AvailableSpace::MinContent if wrap_words => text_min_content_width(text_layout, fonts),
+        // Text shrinks to a single glyph
+        AvailableSpace::MinContent => 0.,
+        AvailableSpace::MaxContent => f32::INFINITY,
+    });
+
+    let mut job = text_layout.clone();
+    // Text that does not wrap is laid out with infinite width
+    if job.wrap.max_width.is_finite() {
+        job.wrap.max_width = width;
+    }
+    let size = fonts.layout_job(job).size().ceil();
+
+    Size {
+        width: known_size.width.unwrap_or(size.x),
+        height: known_size.height.unwrap_or(size.y),
+    }
+}
+
+/// Width of the widest word, text is not wrapped in the middle of a word at this width
+fn text_min_content_width(text_layout: &egui::text::LayoutJob, fonts: &egui::text::Fonts) -> f32 {
+    let mut job = text_layout.clone();
+    job.wrap.max_width = f32::INFINITY;
+    let galley = fonts.layout_job(job);
+
+    let mut width: f32 = 0.;
+    for placed_row in &galley.rows {
+        let mut word_start = None;
+        for glyph in &placed_row.row.glyphs {
+            if glyph.chr.is_whitespace() {
+                word_start = None;
+                continue;
+            }
+            let start = *word_start.get_or_insert(glyph.pos.x);
+            width = width.max(glyph.pos.x + glyph.advance_width - start);
+        }
+    }
+    width
+}
+
+/// Custom leaf node size calculation logic used by taffy layout algorithm
+///
+/// Allows leaf node to calculate its size for any layout constraints without
+/// drawing its content first (For example, keep aspect ratio of an image or plot).
+///
+/// Measure implementation is compared with the one from the previous frame,
+/// layout is recalculated only if it has changed.
+///
+/// Measure is called without holding egui locks, therefore it can use egui context
+/// (For example, lay out text using [`egui::Context::fonts`]). Layout state of the Tui
+/// is in use during layout calculation (See [`TaffyState::with`]).
+///
+/// See [`TuiBuilderLogic::ui_measure`].
+pub trait TuiMeasure: PartialEq + Send + Sync + 'static {
+    /// Calculate node size.
+    ///
+    /// `known_dimensions` contains node size in dimensions that are already determined
+    /// by the layout algorithm. Returned size in these dimensions is ignored.
+    ///
+    /// `available_space` describes space that is available for the node in its parent node.
+    fn measure(
+        &self,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Size<f32>;
+}
+
+/// Object safe version of [`TuiMeasure`]
+trait TuiMeasureDyn: Send + Sync {
+    fn measure_dyn(
+        &self,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Size<f32>;
+
+    fn as_any(&self) -> &dyn std::any::Any;
+
+    fn eq_dyn(&self, other: &dyn TuiMeasureDyn) -> bool;
+}
+
+impl<T: TuiMeasure> TuiMeasureDyn for T {
+    #[inline]
+    fn measure_dyn(
+        &self,
+        known_dimensions: Size<Option<f32>>,
+        available_space: Size<AvailableSpace>,
+    ) -> Size<f32> {
+        self.measure(known_dimensions, available_space)
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    #[inline]
+    fn eq_dyn(&self, other: &dyn TuiMeasureDyn) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+/// Type erased [`TuiMeasure`] implementation
+#[derive(Clone)]
+pub struct BoxedTuiMeasure(Arc<dyn TuiMeasureDyn>);
+
+impl BoxedTuiMeasure {
+    /// Type erase custom measure implementation
+    pub fn new(measure: impl TuiMeasure) -> Self {
+        Self(Arc::new(measure))
+    }
+}
+
+impl PartialEq for BoxedTuiMeasure {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0.eq_dyn(other.0.as_ref())
+    }
 }
 
 /// Helper to show the inner content of a container.
@@ -872,6 +2356,8 @@ pub struct TaffyContainerUi {
     parent_rect: egui::Rect,
     last_scroll_offset: egui::Vec2,
     sticky: egui::Vec2b,
+    /// Offset of sticky node from its normal position (See [`TuiBuilderLogic::position_sticky`])
+    sticky_delta: egui::Vec2,
     first_frame: bool,
 }
 
@@ -882,12 +2368,47 @@ impl Default for TaffyContainerUi {
             parent_rect: egui::Rect::ZERO,
             last_scroll_offset: Default::default(),
             sticky: Default::default(),
+            sticky_delta: Default::default(),
             first_frame: Default::default(),
         }
     }
 }
 
 #[inline]
+/// Index of the next shape that will be painted in the ui layer
+fn layer_next_shape_idx(ui: &egui::Ui) -> usize {
+    ui.ctx().graphics(|graphics| {
+        graphics
+            .get(ui.layer_id())
+            .map_or(0, |paint_list| paint_list.next_idx().0)
+    })
+}
+
+/// Move shapes painted in the ui layer starting from index `from` so that they start at
+/// index `to` (`to <= from`), shapes in between are moved after them.
+/// Returns the number of moved shapes.
+fn move_painted_shapes(ui: &egui::Ui, from: usize, to: usize) -> usize {
+    ui.ctx().graphics_mut(|graphics| {
+        let paint_list = graphics.entry(ui.layer_id());
+        let end = paint_list.next_idx().0;
+        if to >= from || from >= end {
+            return end - from.min(end);
+        }
+
+        let mut shapes: Vec<egui::epaint::ClippedShape> = paint_list
+            .all_entries()
+            .skip(to)
+            .take(end - to)
+            .cloned()
+            .collect();
+        shapes.rotate_left(from - to);
+        for (idx, shape) in (to..end).zip(shapes) {
+            paint_list.set(egui::layers::ShapeIdx(idx), shape.clip_rect, shape.shape);
+        }
+        end - from
+    })
+}
+
 fn sum_axis(rect: &taffy::Rect<f32>) -> taffy::Size<f32> {
     taffy::Size {
         width: rect.left + rect.right,
@@ -915,7 +2436,14 @@ impl TaffyContainerUi {
     /// Sticky element compensation amount based on last scrollable ancestor scroll offset
     #[inline]
     pub fn sticky_offset(&self) -> egui::Vec2 {
-        self.sticky.to_vec2() * self.last_scroll_offset
+        self.sticky.to_vec2() * self.last_scroll_offset - self.sticky_delta
+    }
+
+    /// Is sticky node moved from its normal position in specified dimensions
+    /// (See [`TuiBuilderLogic::position_sticky`])
+    #[inline]
+    pub fn stuck(&self) -> egui::Vec2b {
+        egui::Vec2b::new(self.sticky_delta.x != 0., self.sticky_delta.y != 0.)
     }
 
     /// Full container size
@@ -924,6 +2452,16 @@ impl TaffyContainerUi {
         self.full_container_with(true)
     }
 
+    /// Full container rect including margin
+    #[inline]
+    pub fn full_container_with_margin(&self) -> egui::Rect {
+        let margin = &self.layout.margin;
+        let mut rect = self.full_container();
+        rect.min -= egui::Vec2::new(margin.left, margin.top);
+        rect.max += egui::Vec2::new(margin.right, margin.bottom);
+        rect
+    }
+
     /// Full container size
     #[inline]
     pub fn full_container_with(&self, scroll_offset: bool) -> egui::Rect {
@@ -1010,6 +2548,16 @@ pub struct TuiContainerResponse<T> {
     pub max_size: egui::Vec2,
     /// Can widget grow to infinite size in given dimensions
     pub infinite: egui::Vec2b,
+    /// Text layout of the widget.
+    ///
+    /// If set, layout algorithm lays out this text at the exact width
+    /// it is measuring to calculate wrapped text height instead of using other size constraints.
+    /// Text is not wrapped if [`egui::text::TextWrapping::max_width`] is infinite.
+    pub text_layout: Option<Arc<egui::text::LayoutJob>>,
+    /// Custom size calculation logic for layout algorithm.
+    ///
+    /// If set, other size constraints are ignored.
+    pub measure: Option<BoxedTuiMeasure>,
 }
 
 /// Return values from Main, Background closures
@@ -1044,7 +2592,7 @@ pub trait TuiWidget {
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Id type to simplify defining layout node ids
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Debug)]
 pub enum TuiId {
     /// Create id based on parent node id and given id
     ///
@@ -1061,6 +2609,15 @@ pub enum TuiId {
 }
 
 impl TuiId {
+    /// Name of the id variant
+    fn kind_name(&self) -> &'static str {
+        match self {
+            TuiId::Hiarchy(_) => "Hiarchy",
+            TuiId::Unique(_) => "Unique",
+            TuiId::Auto => "Auto",
+        }
+    }
+
     /// Calculate final id based on the current Tui state
     fn resolve(self, tui: &Tui) -> egui::Id {
         match self {
@@ -1105,14 +2662,112 @@ pub struct TaffyState {
 
     id_to_node_id: HashMap<egui::Id, NodeData>,
 
+    node_to_id: HashMap<NodeId, egui::Id>,
+
+    /// Incremented every time Tui is shown
+    generation: u64,
+
+    /// Nodes detached from their parents during this generation
+    detached_nodes: Vec<NodeId>,
+
     last_size: egui::Vec2,
+
+    root_node: Option<NodeId>,
+
+    style_overrides: HashMap<egui::Id, taffy::Style>,
+
+    /// Distinct node styles (See [`SharedStyle`])
+    styles: StyleInterner,
+
+    highlighted_node: Option<egui::Id>,
+
+    transitions: HashMap<NodeId, TransitionState>,
+
+    memos: HashMap<NodeId, MemoState>,
+
+    /// Nodes that should be scrolled into view
+    scroll_requests: HashMap<egui::Id, ScrollRequest>,
+
+    report: ConvergenceReport,
+
+    settled_paint: Option<SettledPaint>,
+
+    /// State was created and layout cache was not restored yet
+    /// (See [`TuiInitializer::persist_layout`])
+    #[cfg(feature = "serde")]
+    restore_layout_cache: bool,
+
+    /// Layout was recalculated since it was stored in the layout cache
+    #[cfg(feature = "serde")]
+    layout_cache_outdated: bool,
+}
+
+/// Maximal count of frames in which the last settled layout is painted instead of the current one
+const MAX_STALE_FRAMES: u64 = 3;
+
+/// Shapes painted by the last settled layout (See [`TuiInitializer::double_buffer`])
+struct SettledPaint {
+    shapes: Vec<egui::epaint::ClippedShape>,
+    /// Root rectangle at the time of painting
+    rect: egui::Rect,
+    /// First frame since settled layout is painted instead of the current one
+    stale_since_frame: Option<u64>,
 }
 
 /// Stores information about node that was identified by egui::Id
 pub struct NodeData {
     /// [`taffy::TaffyTree`] node id
     pub node_id: NodeId,
-    keep: bool,
+    /// Last generation (See [`TaffyState::generation`]) in which node was added
+    generation: u64,
+    sticky: egui::Vec2b,
+    stuck: egui::Vec2b,
+    scroll_offset: egui::Vec2,
+    scroll_info: Option<ScrollInfo>,
+    /// Node rectangle in which node was shown in the last frame
+    rect: egui::Rect,
+    animation: Option<NodeAnimation>,
+    /// Interned style that was used to set node style
+    /// (`None` if taffy tree node style was modified directly)
+    style: Option<InternedStyle>,
+    /// Inset of sticky node that is used as sticky threshold instead of taffy style inset
+    /// (See [`TuiBuilderLogic::position_sticky`])
+    sticky_inset: Option<taffy::Rect<LengthPercentageAuto>>,
+}
+
+impl NodeData {
+    /// Is node position sticky in specified dimensions
+    #[inline]
+    pub fn sticky(&self) -> egui::Vec2b {
+        self.sticky
+    }
+
+    /// Is sticky node moved from its normal position in specified dimensions
+    /// (See [`TuiBuilderLogic::position_sticky`])
+    #[inline]
+    pub fn stuck(&self) -> egui::Vec2b {
+        self.stuck
+    }
+
+    /// Scroll offset of node content during the last frame (Zero if node is not scrollable)
+    #[inline]
+    pub fn scroll_offset(&self) -> egui::Vec2 {
+        self.scroll_offset
+    }
+
+    /// Scroll state of node during the last frame in which it was shown
+    /// (`None` if node is not scrollable)
+    #[inline]
+    pub fn scroll_info(&self) -> Option<ScrollInfo> {
+        self.scroll_info
+    }
+
+    /// Node rectangle (border box) in screen points in which node was shown
+    /// during the last frame, including layout animation and sticky offsets
+    #[inline]
+    pub fn rect(&self) -> egui::Rect {
+        self.rect
+    }
 }
 
 impl TaffyState {
@@ -1121,9 +2776,38 @@ impl TaffyState {
             taffy_tree: TaffyTree::new(),
             last_size: egui::Vec2::ZERO,
             id_to_node_id: HashMap::default(),
+            node_to_id: HashMap::default(),
+            generation: 0,
+            detached_nodes: Vec::new(),
+            root_node: None,
+            style_overrides: HashMap::default(),
+            styles: StyleInterner::default(),
+            highlighted_node: None,
+            transitions: HashMap::default(),
+            memos: HashMap::default(),
+            scroll_requests: HashMap::default(),
+            report: ConvergenceReport::default(),
+            settled_paint: None,
+            #[cfg(feature = "serde")]
+            restore_layout_cache: true,
+            #[cfg(feature = "serde")]
+            layout_cache_outdated: false,
         }
     }
 
+    /// Access stored state of egui_taffy instance with given id.
+    ///
+    /// Returns `None` if state does not exist or is currently in use (Tui is being drawn).
+    pub fn with<R>(
+        ctx: &egui::Context,
+        id: egui::Id,
+        f: impl FnOnce(&mut TaffyState) -> R,
+    ) -> Option<R> {
+        let state = ctx.data(|data| data.get_temp::<Arc<parking_lot::Mutex<TaffyState>>>(id))?;
+        let mut state = state.try_lock()?;
+        Some(f(&mut state))
+    }
+
     #[inline]
     fn layout(&self, node_id: NodeId) -> &Layout {
         self.taffy_tree.layout(node_id).unwrap()
@@ -1140,6 +2824,167 @@ impl TaffyState {
     pub fn items(&self) -> &HashMap<egui::Id, NodeData> {
         &self.id_to_node_id
     }
+
+    /// Export computed layout tree as a snapshot (See [`snapshot::LayoutSnapshot`])
+    pub fn layout_snapshot(&self) -> snapshot::LayoutSnapshot {
+        snapshot::LayoutSnapshot::from_state(self)
+    }
+
+    /// Mapping to retrieve [`egui::Id`] of taffy nodes
+    #[inline]
+    pub(crate) fn node_egui_ids(&self) -> &HashMap<NodeId, egui::Id> {
+        &self.node_to_id
+    }
+
+    /// Remove detached nodes and their descendants that were not added during
+    /// the current generation. Cost is proportional to the amount of removed nodes.
+    pub(crate) fn collect_garbage(&mut self) {
+        let mut stack = std::mem::take(&mut self.detached_nodes);
+        while let Some(node_id) = stack.pop() {
+            let Some(id) = self.node_to_id.get(&node_id).copied() else {
+                // Already removed
+                continue;
+            };
+            if self.id_to_node_id[&id].generation == self.generation {
+                // Node was moved to another parent
+                continue;
+            }
+            if self.taffy_tree.parent(node_id).is_some() {
+                // Node is attached to another node that is still in use
+                continue;
+            }
+
+            // Descendants are detached by removing their parent
+            stack.extend(self.taffy_tree.child_ids(node_id));
+            self.taffy_tree.remove(node_id).unwrap();
+            self.node_to_id.remove(&node_id);
+            if let Some(style) = self
+                .id_to_node_id
+                .remove(&id)
+                .and_then(|node_data| node_data.style)
+            {
+                self.styles.release(style);
+            }
+            self.style_overrides.remove(&id);
+            self.transitions.remove(&node_id);
+            self.memos.remove(&node_id);
+        }
+        // Reuse allocation
+        self.detached_nodes = stack;
+    }
+
+    /// Current generation, incremented every time Tui is shown
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Number of distinct node styles stored in layout state
+    /// (Equal styles of different nodes are stored once, see [`SharedStyle`])
+    pub fn style_count(&self) -> usize {
+        self.styles.len()
+    }
+
+    /// Mark layout of every node as dirty, layout is fully recalculated
+    /// the next time Tui is shown
+    pub fn invalidate(&mut self) {
+        for node_data in self.id_to_node_id.values() {
+            self.taffy_tree.mark_dirty(node_data.node_id).unwrap();
+        }
+        self.last_size = egui::Vec2::NAN;
+    }
+
+    /// Drop all cached layout state (nodes, measurements, style overrides and transitions).
+    /// Tui is laid out from scratch the next time it is shown.
+    pub fn reset(&mut self) {
+        *self = Self {
+            // Layout is not restored from the layout cache after explicit reset
+            #[cfg(feature = "serde")]
+            restore_layout_cache: false,
+            ..Self::new()
+        };
+    }
+
+    /// Layout convergence report of the last frame in which the layout was shown.
+    ///
+    /// Report is complete after the frame has ended.
+    #[inline]
+    pub fn convergence_report(&self) -> &ConvergenceReport {
+        &self.report
+    }
+
+    /// Root node of the layout (Available after the first frame)
+    #[inline]
+    pub fn root_node(&self) -> Option<NodeId> {
+        self.root_node
+    }
+
+    /// Override style of the node identified by [`egui::Id`].
+    ///
+    /// Override replaces the style provided by the application until it is removed
+    /// or the node stops being used.
+    pub fn set_style_override(&mut self, id: egui::Id, style: taffy::Style) {
+        self.style_overrides.insert(id, style);
+    }
+
+    /// Remove style override of the node, style provided by the application is used again
+    pub fn remove_style_override(&mut self, id: egui::Id) -> Option<taffy::Style> {
+        self.style_overrides.remove(&id)
+    }
+
+    /// Retrieve style override of the node
+    #[inline]
+    pub fn style_override(&self, id: egui::Id) -> Option<&taffy::Style> {
+        self.style_overrides.get(&id)
+    }
+
+    /// Node which box model is highlighted in the layout
+    #[inline]
+    pub fn highlighted_node(&self) -> Option<egui::Id> {
+        self.highlighted_node
+    }
+
+    /// Highlight node box model in the layout (Used by [`inspector::TuiInspector`])
+    pub fn set_highlighted_node(&mut self, id: Option<egui::Id>) {
+        self.highlighted_node = id;
+    }
+
+    /// Scroll node identified by [`egui::Id`] into view the next time Tui is shown
+    /// (See [`Tui::scroll_to`]). Request repaint to show it immediately.
+    pub fn scroll_to(&mut self, id: egui::Id, align: Option<egui::Align>) {
+        self.scroll_requests
+            .insert(id, ScrollRequest::node(align, None));
+    }
+
+    /// Same as [`TaffyState::scroll_to`], but allows to specify scroll animation
+    pub fn scroll_to_animation(
+        &mut self,
+        id: egui::Id,
+        align: Option<egui::Align>,
+        animation: egui::style::ScrollAnimation,
+    ) {
+        self.scroll_requests
+            .insert(id, ScrollRequest::node(align, Some(animation)));
+    }
+
+    /// Scroll state of scrollable node identified by [`egui::Id`] during the last frame
+    pub fn scroll_info(&self, id: egui::Id) -> Option<ScrollInfo> {
+        self.id_to_node_id.get(&id)?.scroll_info
+    }
+
+    /// Set scroll offset of scrollable node identified by [`egui::Id`]
+    /// the next time Tui is shown (See [`Tui::set_scroll_offset`])
+    pub fn set_scroll_offset(&mut self, id: egui::Id, offset: egui::Vec2) {
+        self.scroll_requests
+            .insert(id, ScrollRequest::offset(offset));
+    }
+
+    /// Drop scroll requests of nodes that do not exist or were not shown for several passes
+    fn expire_scroll_requests(&mut self) {
+        let nodes = &self.id_to_node_id;
+        self.scroll_requests
+            .retain(|id, request| nodes.contains_key(id) && request.retain());
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -1151,6 +2996,81 @@ pub struct TuiBuilder<'r> {
     params: TuiBuilderParams,
 }
 
+/// Immutable node style that is shared between nodes and frames.
+///
+/// Node styles are interned: equal styles of different nodes are stored once in layout state
+/// and nodes keep the interned shared style (See [`TaffyState::style_count`]).
+/// Node remembers the last used shared style, therefore unchanged style is detected
+/// by pointer comparison instead of comparing whole [`taffy::Style`] values.
+/// Keep shared style between frames (for example, in application state) and reuse it
+/// for many similar nodes (rows of large grid or virtual list).
+///
+/// Style set with [`TuiBuilderLogic::style`] is compared by value with the interned style
+/// of the node every frame. Taffy tree still stores its own copy of the style for every node.
+///
+/// ```ignore
+/// let row_style = SharedStyle::new(Style { gap: length(4.), ..Default::default() });
+/// for row in rows {
+///     tui.shared_style(&row_style).add(|tui| { ... });
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SharedStyle(Arc<taffy::Style>);
+
+impl SharedStyle {
+    /// Create shared style
+    pub fn new(style: taffy::Style) -> Self {
+        Self(Arc::new(style))
+    }
+
+    /// Access style
+    #[inline]
+    pub fn style(&self) -> &taffy::Style {
+        &self.0
+    }
+
+    /// Do both values point to the same style instance
+    #[inline]
+    pub fn ptr_eq(&self, other: &SharedStyle) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl From<taffy::Style> for SharedStyle {
+    #[inline]
+    fn from(style: taffy::Style) -> Self {
+        Self::new(style)
+    }
+}
+
+/// Condition in which node content is skipped
+#[derive(Clone, Copy)]
+enum ContentSkip {
+    /// Node has `display: None` style (See [`TuiBuilderLogic::add`])
+    DisplayNone,
+    /// Node is outside of the visible area (See [`TuiBuilderLogic::add_culled`])
+    Offscreen,
+    /// Node memo key and size has not changed (See [`TuiBuilderLogic::add_memo`])
+    Memo(u64),
+}
+
+/// Style used to add node (See [`Tui::add_child_node`])
+#[allow(clippy::large_enum_variant)] // Short lived value, owned style is moved to taffy tree
+enum NodeStyle {
+    Owned(taffy::Style),
+    Shared(SharedStyle),
+}
+
+impl NodeStyle {
+    #[inline]
+    fn style(&self) -> &taffy::Style {
+        match self {
+            NodeStyle::Owned(style) => style,
+            NodeStyle::Shared(style) => style.style(),
+        }
+    }
+}
+
 /// Parameters for creating child element in Tui layout
 #[derive(Clone)]
 pub struct TuiBuilderParams {
@@ -1160,6 +3080,9 @@ pub struct TuiBuilderParams {
     /// Child element taffy layout settings / style
     pub style: Option<taffy::Style>,
 
+    /// Child element shared style, used if `style` is not set (See [`SharedStyle`])
+    pub shared_style: Option<SharedStyle>,
+
     /// Should layout descendant egui ui be disabled upon creation
     pub disabled: bool,
 
@@ -1174,6 +3097,35 @@ pub struct TuiBuilderParams {
 
     /// Sticky position (Should last scroll offset affect the position of the element)
     pub sticky: egui::Vec2b,
+
+    /// Animate node position and size changes between frames
+    pub animation: Option<LayoutAnimation>,
+
+    /// Enter and exit transition of the node
+    pub transition: Option<NodeTransition>,
+
+    /// Node does not create its own child egui ui (See [`TuiBuilderLogic::layout_only`])
+    pub layout_only: bool,
+
+    /// Node content visibility (See [`TuiBuilderLogic::visibility`])
+    pub visibility: Visibility,
+
+    /// Scroll area settings of scrollable node (See [`TuiBuilderLogic::scroll_config`])
+    pub scroll_config: Option<ScrollConfig>,
+
+    /// Node uses CSS `position: sticky` semantics (See [`TuiBuilderLogic::position_sticky`])
+    pub position_sticky: bool,
+}
+
+/// Node content visibility
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Visibility {
+    /// Node content is shown
+    #[default]
+    Visible,
+    /// Node keeps its layout slot, but its content is not painted and
+    /// can not be interacted with. Node content is still executed to keep widget state.
+    Hidden,
 }
 
 impl<'r> TuiBuilder<'r> {
@@ -1206,11 +3158,18 @@ impl<'r> AsTuiBuilder<'r> for &'r mut Tui {
             params: TuiBuilderParams {
                 id: TuiId::Auto,
                 style: None,
+                shared_style: None,
                 disabled: false,
                 wrap_mode: None,
                 egui_style: None,
                 layout: None,
                 sticky: egui::Vec2b::FALSE,
+                animation: None,
+                transition: None,
+                layout_only: false,
+                visibility: Visibility::Visible,
+                scroll_config: None,
+                position_sticky: false,
             },
         }
     }
@@ -1312,7 +3271,52 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
     #[inline]
     fn mut_style(self, f: impl FnOnce(&mut taffy::Style)) -> Self::Builder {
         let mut tui = self.tui();
-        f(tui.params_mut().style.get_or_insert_with(Default::default));
+        let params = tui.params_mut();
+        let shared_style = params.shared_style.take();
+        f(params.style.get_or_insert_with(|| {
+            shared_style
+                .map(|style| style.style().clone())
+                .unwrap_or_default()
+        }));
+        tui
+    }
+
+    /// Set child node content visibility (See [`Visibility`]).
+    ///
+    /// Use `display: None` style to remove node from the layout.
+    #[inline]
+    fn visibility(self, visibility: Visibility) -> Self::Builder {
+        let mut tui = self.tui();
+        tui.params_mut().visibility = visibility;
+        tui
+    }
+
+    /// Declare child node as layout only node that is used only to place its child nodes.
+    ///
+    /// Layout only node does not create its own child egui ui, its children are placed
+    /// directly in the parent ui. This avoids per node egui overhead in deeply nested layouts.
+    ///
+    /// Child egui ui is still created if node draws background, changes egui ui settings
+    /// (style, layout, wrap mode, disabled), uses transition or clips / scrolls its overflow.
+    /// Leaf nodes that contain egui widgets always create child ui.
+    ///
+    /// Content drawn directly with [`Tui::egui_ui_mut`] in layout only node is added to the
+    /// parent ui.
+    #[inline]
+    fn layout_only(self) -> Self::Builder {
+        let mut tui = self.tui();
+        tui.params_mut().layout_only = true;
+        tui
+    }
+
+    /// Set child node style that is shared between nodes and frames.
+    ///
+    /// Unchanged shared style is detected without comparing style values (See [`SharedStyle`]).
+    #[inline]
+    fn shared_style(self, style: &SharedStyle) -> Self::Builder {
+        let mut tui = self.tui();
+        tui.params_mut().style = None;
+        tui.params_mut().shared_style = Some(style.clone());
         tui
     }
 
@@ -1386,12 +3390,117 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
         tui
     }
 
+    /// Position node like CSS `position: sticky`.
+    ///
+    /// Node is laid out at its normal position. While its nearest scrollable ancestor
+    /// (`overflow: Scroll` node or the Tui clip rectangle) is scrolled, node is kept at `inset`
+    /// distance from the visible area edges, but it is never moved outside of its parent
+    /// content box (scrolled content of the scrollable parent).
+    /// Therefore a section header with `inset.top = length(8.)` sticks at the top only while
+    /// its section is in view and is pushed away by the next section.
+    ///
+    /// Stuck node is painted and interacted with in a sublayer above the Tui layer,
+    /// therefore it is above the content that follows it. Node is kept in the Tui layer
+    /// (painted in normal order) when Tui uses [`TuiInitializer::double_buffer`] or when its
+    /// ancestor is memoized or uses transition, because their stored shapes are replayed from it.
+    /// See [`Tui::stuck`] and [`TaffyContainerUi::stuck`].
+    #[inline]
+    fn position_sticky(self) -> Self::Builder {
+        let mut tui = self.tui();
+        tui.params_mut().position_sticky = true;
+        tui
+    }
+
+    /// Set scroll area settings of scrollable node (`overflow: Scroll`).
+    ///
+    /// See [`ScrollConfig`].
+    #[inline]
+    fn scroll_config(self, config: ScrollConfig) -> Self::Builder {
+        let mut tui = self.tui();
+        tui.params_mut().scroll_config = Some(config);
+        tui
+    }
+
+    /// Animate node position and size changes between frames.
+    ///
+    /// See [`LayoutAnimation`].
+    #[inline]
+    fn animate_layout(self, animation: LayoutAnimation) -> Self::Builder {
+        let mut tui = self.tui();
+        tui.params_mut().animation = Some(animation);
+        tui
+    }
+
+    /// Fade / grow node in when it appears and fade / collapse it out when it is removed.
+    ///
+    /// See [`NodeTransition`].
+    #[inline]
+    fn transition(self, transition: NodeTransition) -> Self::Builder {
+        let mut tui = self.tui();
+        tui.params_mut().transition = Some(transition);
+        tui
+    }
+
+    /// Add tui node as children to this node, node content is skipped while node is
+    /// outside of the visible area (clip rect of the parent ui, e.g., scroll area viewport).
+    ///
+    /// Culled node keeps its child nodes and their previous layout in the taffy tree,
+    /// therefore node reserves its last size. Visibility is checked using node layout
+    /// from the last layout calculation. Returns `None` if node content was skipped.
+    ///
+    /// Useful for long scrollable documents that consist of many heterogeneous sections.
+    ///
+    /// Content of hidden node (`display: None` style or [`Visibility::Hidden`]) is skipped too,
+    /// node keeps its child nodes, therefore their state is not lost.
+    #[inline]
+    fn add_culled<T>(self, f: impl FnOnce(&mut Tui) -> T) -> Option<T> {
+        let tui = self.tui().unpack();
+        tui.builder_tui
+            .add_child_skippable(
+                tui.params,
+                (),
+                |tui, _| Some(f(tui)),
+                Some((ContentSkip::Offscreen, None)),
+            )
+            .main
+    }
+
+    /// Add memoized tui node as children to this node.
+    ///
+    /// If `key`, node size, egui style and pixels per point are the same as in the last frame,
+    /// content closure is not executed. Node keeps its child nodes in the taffy tree and shapes
+    /// painted by the node content in the last frame are replayed at the current node position.
+    /// Content is executed again if larger part of the node becomes visible.
+    ///
+    /// Key should describe all inputs of the content (e.g. card data or its version).
+    /// Replayed content is not interactive (widgets are not created), therefore memoization
+    /// is suitable for static content. Shapes painted in other layers are not replayed.
+    ///
+    /// Content of hidden node (`display: None` style or [`Visibility::Hidden`]) is skipped.
+    #[inline]
+    fn add_memo(self, key: impl std::hash::Hash, f: impl FnOnce(&mut Tui)) {
+        let key = egui::Id::new(key).value();
+        let tui = self.tui().unpack();
+        tui.builder_tui
+            .add_child_skippable(
+                tui.params,
+                (),
+                |tui, _| f(tui),
+                Some((ContentSkip::Memo(key), ())),
+            )
+            .main
+    }
+
     /// Add tui node as children to this node
+    ///
+    /// Content of node with `display: None` style is skipped and `None` is returned,
+    /// node keeps its child nodes, therefore their state is not lost.
+    /// Content of [`Visibility::Hidden`] node is executed in invisible and disabled ui.
     #[inline]
-    fn add<T>(self, f: impl FnOnce(&mut Tui) -> T) -> T {
+    fn add<T>(self, f: impl FnOnce(&mut Tui) -> T) -> Option<T> {
         let tui = self.tui().unpack();
         tui.builder_tui
-            .add_child(tui.params, (), |tui, _| f(tui))
+            .add_child_displayed(tui.params, (), |tui, _| f(tui))
             .main
     }
 
@@ -1400,13 +3509,15 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
     /// Useful to fill grid cells with empty content
     #[inline]
     fn add_empty(self) {
-        self.add(|_| {})
+        self.add(|_| {});
     }
 
     /// Add tui node as children to this node and draw only background color
+    ///
+    /// Returns `None` if node has `display: None` style (See [`TuiBuilderLogic::add`])
     #[inline]
-    fn add_with_background_color<T>(self, f: impl FnOnce(&mut Tui) -> T) -> T {
-        let tui = self.tui();
+    fn add_with_background_color<T>(self, f: impl FnOnce(&mut Tui) -> T) -> Option<T> {
+        let tui = self.tui().unpack();
 
         fn background(ui: &mut egui::Ui, container: &TaffyContainerUi) {
             // TODO: Expand added to fill rounded gaps between elements
@@ -1423,13 +3534,17 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
                 .rect_filled(rect, visuals.corner_radius, window_fill);
         }
 
-        tui.add_with_background_ui(background, |tui, _| f(tui)).main
+        tui.builder_tui
+            .add_child_displayed(tui.params, background, |tui, _| f(tui))
+            .main
     }
 
     /// Add tui node as children to this node and draw popup background
+    ///
+    /// Returns `None` if node has `display: None` style (See [`TuiBuilderLogic::add`])
     #[inline]
-    fn add_with_background<T>(self, f: impl FnOnce(&mut Tui) -> T) -> T {
-        let tui = self.tui().with_border_style_from_egui_style();
+    fn add_with_background<T>(self, f: impl FnOnce(&mut Tui) -> T) -> Option<T> {
+        let tui = self.tui().with_border_style_from_egui_style().unpack();
 
         fn background(ui: &mut egui::Ui, container: &TaffyContainerUi) {
             let rect = container.full_container();
@@ -1450,7 +3565,9 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
             );
         }
 
-        let return_values = tui.add_with_background_ui(background, |tui, _| f(tui));
+        let return_values =
+            tui.builder_tui
+                .add_child_displayed(tui.params, background, |tui, _| f(tui));
         return_values.main
     }
 
@@ -1477,8 +3594,10 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
 
     /// Add tui node as children to this node and draw simple group Frame background
     /// using styling from egui
+    ///
+    /// Returns `None` if node has `display: None` style (See [`TuiBuilderLogic::add`])
     #[inline]
-    fn add_with_border<T>(self, f: impl FnOnce(&mut Tui) -> T) -> T {
+    fn add_with_border<T>(self, f: impl FnOnce(&mut Tui) -> T) -> Option<T> {
         fn background(ui: &mut egui::Ui, container: &TaffyContainerUi) {
             let visuals = ui.style().noninteractive();
             let rect = container.full_container();
@@ -1493,16 +3612,19 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
             );
         }
 
-        let return_values = self
-            .with_border_style_from_egui_style()
-            .add_with_background_ui(background, |tui, _| f(tui));
+        let tui = self.with_border_style_from_egui_style().unpack();
+        let return_values =
+            tui.builder_tui
+                .add_child_displayed(tui.params, background, |tui, _| f(tui));
         return_values.main
     }
 
     /// Add tui node as children to this node and draw simple group Frame background
     /// using styling from taffy
+    ///
+    /// Returns `None` if node has `display: None` style (See [`TuiBuilderLogic::add`])
     #[inline]
-    fn add_with_taffy_border<T>(self, f: impl FnOnce(&mut Tui) -> T) -> T {
+    fn add_with_taffy_border<T>(self, f: impl FnOnce(&mut Tui) -> T) -> Option<T> {
         fn background(ui: &mut egui::Ui, container: &TaffyContainerUi) {
             let visuals = ui.style().noninteractive();
             let rect = container.full_container();
@@ -1568,7 +3690,10 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
             }
         }
 
-        let return_values = self.add_with_background_ui(background, |tui, _| f(tui));
+        let tui = self.tui().unpack();
+        let return_values =
+            tui.builder_tui
+                .add_child_displayed(tui.params, background, |tui, _| f(tui));
         return_values.main
     }
 
@@ -1714,6 +3839,9 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
 
     /// Add tui node as children to this node and draw custom background
     ///
+    /// Content of hidden node (`display: None` style or [`Visibility::Hidden`]) is executed
+    /// in invisible and disabled ui.
+    ///
     /// See [`TuiBuilderLogic::add_with_background`] for example
     #[inline]
     fn add_with_background_ui<FR, BR>(
@@ -1742,6 +3870,8 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
                 intrinsic_size: None,
                 max_size: ui.min_size(),
                 infinite: egui::Vec2b::FALSE,
+                text_layout: None,
+                measure: None,
             }
         })
     }
@@ -1757,6 +3887,8 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
                 intrinsic_size: None,
                 max_size: ui.min_size(),
                 infinite: egui::Vec2b::TRUE,
+                text_layout: None,
+                measure: None,
             }
         })
     }
@@ -1774,6 +3906,25 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
         tui.builder_tui.add_container(tui.params, content)
     }
 
+    /// Add egui ui as tui leaf node which size is calculated by given [`TuiMeasure`]
+    ///
+    /// Content is shown in the space calculated by layout algorithm.
+    #[inline]
+    fn ui_measure<T>(self, measure: impl TuiMeasure, content: impl FnOnce(&mut Ui) -> T) -> T {
+        self.ui_manual(|ui, _params| {
+            let inner = content(ui);
+            TuiContainerResponse {
+                inner,
+                min_size: ui.min_size(),
+                intrinsic_size: None,
+                max_size: ui.min_size(),
+                infinite: egui::Vec2b::FALSE,
+                text_layout: None,
+                measure: Some(BoxedTuiMeasure::new(measure)),
+            }
+        })
+    }
+
     /// Add tui or egui widget that implements [`TuiWidget`]` as leaf node
     #[inline]
     fn ui_add<T: TuiWidget>(self, widget: T) -> T::Response {
@@ -1800,6 +3951,8 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
                 intrinsic_size: response.intrinsic_size,
                 max_size: response.rect.size(),
                 infinite: egui::Vec2b::FALSE,
+                text_layout: None,
+                measure: None,
                 inner: response,
             };
 
@@ -1810,31 +3963,31 @@ pub trait TuiBuilderLogic<'r>: AsTuiBuilder<'r> + Sized {
     /// Add egui label as child node
     #[inline]
     fn label(self, text: impl Into<egui::WidgetText>) -> Response {
-        egui::Label::new(text).taffy_ui(self.tui().unpack())
+        egui_widgets::text_label(self.tui().unpack(), text.into())
     }
 
     /// Add egui colored label as child node
     #[inline]
     fn colored_label(self, color: egui::Color32, text: impl Into<egui::RichText>) -> Response {
-        egui::Label::new(text.into().color(color)).taffy_ui(self.tui().unpack())
+        egui_widgets::text_label(self.tui().unpack(), text.into().color(color).into())
     }
 
     /// Add label as child node with strong visual formatting
     #[inline]
     fn strong(self, text: impl Into<egui::RichText>) -> Response {
-        egui::Label::new(text.into().strong()).taffy_ui(self.tui().unpack())
+        egui_widgets::text_label(self.tui().unpack(), text.into().strong().into())
     }
 
     /// Add egui heading as child node
     #[inline]
     fn heading(self, text: impl Into<egui::RichText>) -> Response {
-        egui::Label::new(text.into().heading()).taffy_ui(self.tui().unpack())
+        egui_widgets::text_label(self.tui().unpack(), text.into().heading().into())
     }
 
     /// Add egui small text as child node
     #[inline]
     fn small(self, text: impl Into<egui::RichText>) -> Response {
-        egui::Label::new(text.into().small()).taffy_ui(self.tui().unpack())
+        egui_widgets::text_label(self.tui().unpack(), text.into().small().into())
     }
 
     /// Add egui separator  as child node
@@ -1878,6 +4031,9 @@ trait BackgroundDraw {
     /// Value returned by background drawing functionality
     type ReturnValue;
 
+    /// Background drawing does nothing
+    const IS_EMPTY: bool = false;
+
     /// Implements background drawing functionality
     fn draw(self, ui: &mut egui::Ui, container: &TaffyContainerUi) -> Self::ReturnValue;
 }
@@ -1897,6 +4053,8 @@ where
 impl BackgroundDraw for () {
     type ReturnValue = ();
 
+    const IS_EMPTY: bool = true;
+
     #[inline]
     fn draw(self, ui: &mut egui::Ui, container: &TaffyContainerUi) -> Self::ReturnValue {
         let _ = container;
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use egui::epaint::ClippedShape;
+
+/// Inputs of memoized content that are not part of the memo key
+#[derive(Clone)]
+pub(crate) struct MemoEnv {
+    /// Style of the parent ui
+    pub style: Arc<egui::Style>,
+    /// Node egui style override (See [`crate::TuiBuilderLogic::egui_style`])
+    pub node_style: Option<Arc<egui::Style>>,
+    /// Node text wrap mode (See [`crate::TuiBuilderLogic::wrap_mode`])
+    pub wrap_mode: Option<egui::TextWrapMode>,
+    pub pixels_per_point: f32,
+}
+
+impl MemoEnv {
+    fn matches(&self, other: &MemoEnv) -> bool {
+        fn style_eq(a: &Arc<egui::Style>, b: &Arc<egui::Style>) -> bool {
+            // Style is usually shared between frames, compare values only if it was recreated
+            Arc::ptr_eq(a, b) || a == b
+        }
+
+        style_eq(&self.style, &other.style)
+            && match (&self.node_style, &other.node_style) {
+                (Some(a), Some(b)) => style_eq(a, b),
+                (None, None) => true,
+                _ => false,
+            }
+            && self.wrap_mode == other.wrap_mode
+            && self.pixels_per_point == other.pixels_per_point
+    }
+}
+
+/// Recorded content of memoized node (See [`crate::TuiBuilderLogic::add_memo`])
+pub(crate) struct MemoState {
+    /// Hash of the memo key
+    key: u64,
+    env: MemoEnv,
+    /// Node rectangle at the time of recording
+    rect: egui::Rect,
+    /// Visible part of the node at the time of recording, relative to the node position
+    visible: egui::Rect,
+    /// Generation in which content was recorded (See [`crate::TaffyState::generation`])
+    pub generation: u64,
+    /// Shapes painted by node and its descendants
+    shapes: Vec<ClippedShape>,
+}
+
+impl MemoState {
+    pub(crate) fn new(
+        key: u64,
+        env: MemoEnv,
+        rect: egui::Rect,
+        clip_rect: egui::Rect,
+        generation: u64,
+        shapes: Vec<ClippedShape>,
+    ) -> Self {
+        Self {
+            key,
+            env,
+            rect,
+            visible: relative_visible_rect(rect, clip_rect),
+            generation,
+            shapes,
+        }
+    }
+
+    /// Can recorded content be reused for node with given key and rectangle.
+    ///
+    /// Node may be moved, but its size must be the same and its visible part must not
+    /// be larger than at the time of recording (content may skip invisible parts).
+    #[inline]
+    pub(crate) fn matches(
+        &self,
+        key: u64,
+        env: &MemoEnv,
+        rect: egui::Rect,
+        clip_rect: egui::Rect,
+    ) -> bool {
+        self.key == key
+            && self.rect.size() == rect.size()
+            && self.env.matches(env)
+            && self
+                .visible
+                .contains_rect(relative_visible_rect(rect, clip_rect))
+    }
+
+    /// Paint recorded shapes at the given node rectangle, shapes are clipped by
+    /// the current ui clip rectangle
+    pub(crate) fn paint(&self, ui: &egui::Ui, rect: egui::Rect) {
+        let delta = rect.min - self.rect.min;
+        let clip_rect = ui.clip_rect();
+        for clipped_shape in &self.shapes {
+            let mut shape = clipped_shape.shape.clone();
+            shape.translate(delta);
+            ui.painter()
+                .with_clip_rect(
+                    clipped_shape
+                        .clip_rect
+                        .translate(delta)
+                        .intersect(clip_rect),
+                )
+                .add(shape);
+        }
+    }
+}
+
+/// Visible part of the node rectangle relative to the node position
+fn relative_visible_rect(rect: egui::Rect, clip_rect: egui::Rect) -> egui::Rect {
+    clip_rect.intersect(rect).translate(-rect.min.to_vec2())
+}
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use taffy::prelude::*;
+
+use crate::{Context, NodeData, TaffyState};
+
+/// Measured layout of egui_taffy instance stored in egui persisted memory
+/// (See [`crate::TuiInitializer::persist_layout`])
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub(crate) struct LayoutCache {
+    root: Option<egui::Id>,
+    /// Nodes in depth first order
+    nodes: Vec<CachedNode>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedNode {
+    id: egui::Id,
+    style: Style,
+    context: Option<CachedContext>,
+    children: Vec<egui::Id>,
+}
+
+/// Serializable part of leaf node [`Context`]
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedContext {
+    min_size: egui::Vec2,
+    max_size: egui::Vec2,
+    infinite: egui::Vec2b,
+    text_layout: Option<egui::text::LayoutJob>,
+}
+
+impl LayoutCache {
+    /// Id of stored layout cache for egui_taffy instance with given id
+    pub(crate) fn id(tui_id: egui::Id) -> egui::Id {
+        tui_id.with("egui_taffy_layout_cache")
+    }
+
+    pub(crate) fn from_state(state: &TaffyState) -> Self {
+        let node_ids = state.node_egui_ids();
+        let taffy_tree = state.taffy_tree();
+
+        let mut nodes = Vec::new();
+        let mut stack: Vec<NodeId> = state.root_node().into_iter().collect();
+        while let Some(node_id) = stack.pop() {
+            let Some(&id) = node_ids.get(&node_id) else {
+                continue;
+            };
+
+            let children = taffy_tree.child_ids(node_id).collect::<Vec<_>>();
+            stack.extend(children.iter().rev());
+
+            nodes.push(CachedNode {
+                id,
+                style: taffy_tree.style(node_id).unwrap().clone(),
+                context: taffy_tree
+                    .get_node_context(node_id)
+                    .and_then(CachedContext::from_context),
+                children: children
+                    .iter()
+                    .filter_map(|child| node_ids.get(child).copied())
+                    .collect(),
+            });
+        }
+
+        Self {
+            root: state
+                .root_node()
+                .and_then(|root| node_ids.get(&root).copied()),
+            nodes,
+        }
+    }
+
+    /// Restore stored nodes in empty layout state, returns restored root node
+    pub(crate) fn restore(self, state: &mut TaffyState) -> Option<NodeId> {
+        let root = self.root?;
+
+        let mut node_ids = HashMap::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let node_id = state.taffy_tree.new_leaf(node.style.clone()).unwrap();
+            if let Some(context) = &node.context {
+                state
+                    .taffy_tree
+                    .set_node_context(node_id, Some(context.to_context()))
+                    .unwrap();
+            }
+            state.node_to_id.insert(node_id, node.id);
+            state.id_to_node_id.insert(
+                node.id,
+                NodeData {
+                    node_id,
+                    generation: 0,
+                    sticky: egui::Vec2b::FALSE,
+                    stuck: egui::Vec2b::FALSE,
+                    scroll_offset: egui::Vec2::ZERO,
+                    scroll_info: None,
+                    rect: egui::Rect::NOTHING,
+                    animation: None,
+                    style: None,
+                    sticky_inset: None,
+                },
+            );
+            node_ids.insert(node.id, node_id);
+        }
+
+        for node in &self.nodes {
+            let children: Vec<NodeId> = node
+                .children
+                .iter()
+                .filter_map(|child| node_ids.get(child).copied())
+                .collect();
+            state
+                .taffy_tree
+                .set_children(node_ids[&node.id], &children)
+                .unwrap();
+        }
+
+        let root_node = node_ids.get(&root).copied();
+        state.root_node = root_node;
+        root_node
+    }
+}
+
+impl CachedContext {
+    /// Custom measure implementations can not be stored, such nodes are measured again
+    fn from_context(context: &Context) -> Option<Self> {
+        if context.measure.is_some() {
+            return None;
+        }
+
+        Some(Self {
+            min_size: context.min_size,
+            max_size: context.max_size,
+            infinite: context.infinite,
+            text_layout: context.text_layout.as_deref().cloned(),
+        })
+    }
+
+    fn to_context(&self) -> Context {
+        Context {
+            min_size: self.min_size,
+            max_size: self.max_size,
+            infinite: self.infinite,
+            text_layout: self.text_layout.clone().map(Arc::new),
+            measure: None,
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use taffy::prelude::*;
+
+/// Update `parent` children in taffy tree to match given `children` order.
+///
+/// Children are matched by their [`NodeId`] (which is keyed by [`egui::Id`] in [`crate::TaffyState`]).
+/// Children that keep their relative order between frames are not touched,
+/// only removed, inserted and moved children are detached or attached to the parent.
+///
+/// Moved children are chosen using the longest increasing subsequence of previous child order,
+/// therefore the amount of moved children is minimal.
+///
+/// Detached children are pushed to `detached`, they are removed from the tree
+/// if they are not used anywhere else (See [`crate::TaffyState::collect_garbage`]).
+pub(crate) fn reconcile_children<C>(
+    tree: &mut TaffyTree<C>,
+    parent: NodeId,
+    children: &[NodeId],
+    detached: &mut Vec<NodeId>,
+) {
+    if tree.child_ids(parent).eq(children.iter().copied()) {
+        // Fast path, nothing changed
+        return;
+    }
+
+    // Target position of every child. On id collision first occurrence wins.
+    let mut target_index: HashMap<NodeId, usize> = HashMap::with_capacity(children.len());
+    for &child in children {
+        let len = target_index.len();
+        target_index.entry(child).or_insert(len);
+    }
+
+    // Target positions of previous children that are still used
+    let previous = tree.children(parent).unwrap();
+    let mut retained: Vec<(usize, usize)> = Vec::with_capacity(previous.len());
+    let mut seen = vec![false; target_index.len()];
+    for (idx, child) in previous.iter().enumerate() {
+        if let Some(&target) = target_index.get(child) {
+            if !seen[target] {
+                seen[target] = true;
+                retained.push((idx, target));
+            }
+        }
+    }
+
+    let targets: Vec<usize> = retained.iter().map(|(_, target)| *target).collect();
+    let stays = longest_increasing_subsequence(&targets);
+
+    let mut keep = vec![false; previous.len()];
+    for ((idx, _), stays) in retained.iter().zip(stays) {
+        keep[*idx] = stays;
+    }
+
+    // Detach removed and moved children (from the back to keep indices valid)
+    for (idx, keep) in keep.iter().enumerate().rev() {
+        if !keep {
+            detached.push(tree.remove_child_at_index(parent, idx).unwrap());
+        }
+    }
+
+    // Attach new and moved children at their final position
+    let mut idx = 0;
+    for &child in children {
+        if target_index[&child] != idx {
+            // Duplicated id, child is already placed
+            continue;
+        }
+
+        let in_place =
+            idx < tree.child_count(parent) && tree.child_at_index(parent, idx).unwrap() == child;
+        if !in_place {
+            if let Some(previous_parent) = tree.parent(child) {
+                // Node moved from another parent node
+                tree.remove_child(previous_parent, child).unwrap();
+            }
+            tree.insert_child_at_index(parent, idx, child).unwrap();
+        }
+        idx += 1;
+    }
+}
+
+/// Mark values that belong to the longest strictly increasing subsequence
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<bool> {
+    // Index into `values` of the smallest tail value for every subsequence length
+    let mut tails: Vec<usize> = Vec::with_capacity(values.len());
+    // Previous element in the subsequence for every element
+    let mut predecessor: Vec<Option<usize>> = vec![None; values.len()];
+
+    for (idx, &value) in values.iter().enumerate() {
+        let pos = tails.partition_point(|&tail| values[tail] < value);
+        if pos > 0 {
+            predecessor[idx] = Some(tails[pos - 1]);
+        }
+        if pos == tails.len() {
+            tails.push(idx);
+        } else {
+            tails[pos] = idx;
+        }
+    }
+
+    let mut result = vec![false; values.len()];
+    let mut current = tails.last().copied();
+    while let Some(idx) = current {
+        result[idx] = true;
+        current = predecessor[idx];
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree_with_children(count: usize) -> (TaffyTree<()>, NodeId, Vec<NodeId>) {
+        let mut tree = TaffyTree::new();
+        let children: Vec<NodeId> = (0..count)
+            .map(|_| tree.new_leaf(Style::default()).unwrap())
+            .collect();
+        let parent = tree.new_with_children(Style::default(), &children).unwrap();
+        (tree, parent, children)
+    }
+
+    #[test]
+    fn longest_increasing_subsequence_marks_kept_values() {
+        assert_eq!(longest_increasing_subsequence(&[]), Vec::<bool>::new());
+        assert_eq!(longest_increasing_subsequence(&[0, 1, 2]), [true; 3]);
+        assert_eq!(
+            longest_increasing_subsequence(&[2, 0, 1]),
+            [false, true, true]
+        );
+        assert_eq!(
+            longest_increasing_subsequence(&[3, 1, 2, 0, 4]),
+            [false, true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn unchanged_children_are_not_touched() {
+        let (mut tree, parent, children) = tree_with_children(3);
+        let mut detached = Vec::new();
+
+        reconcile_children(&mut tree, parent, &children, &mut detached);
+
+        assert_eq!(tree.children(parent).unwrap(), children);
+        assert!(detached.is_empty());
+    }
+
+    #[test]
+    fn moved_child_is_the_only_detached_child() {
+        let (mut tree, parent, children) = tree_with_children(4);
+        let mut detached = Vec::new();
+
+        // Last child is moved to the front
+        let order = [children[3], children[0], children[1], children[2]];
+        reconcile_children(&mut tree, parent, &order, &mut detached);
+
+        assert_eq!(tree.children(parent).unwrap(), order);
+        assert_eq!(detached, [children[3]]);
+    }
+
+    #[test]
+    fn removed_and_inserted_children() {
+        let (mut tree, parent, children) = tree_with_children(3);
+        let new_child = tree.new_leaf(Style::default()).unwrap();
+        let mut detached = Vec::new();
+
+        let order = [children[0], new_child, children[2]];
+        reconcile_children(&mut tree, parent, &order, &mut detached);
+
+        assert_eq!(tree.children(parent).unwrap(), order);
+        assert_eq!(detached, [children[1]]);
+        assert_eq!(tree.parent(new_child), Some(parent));
+    }
+
+    #[test]
+    fn child_moved_from_another_parent() {
+        let (mut tree, parent, children) = tree_with_children(2);
+        let other_parent = tree.new_leaf(Style::default()).unwrap();
+        let moved = tree.new_leaf(Style::default()).unwrap();
+        tree.add_child(other_parent, moved).unwrap();
+        let mut detached = Vec::new();
+
+        let order = [children[0], moved, children[1]];
+        reconcile_children(&mut tree, parent, &order, &mut detached);
+
+        assert_eq!(tree.children(parent).unwrap(), order);
+        assert!(tree.children(other_parent).unwrap().is_empty());
+    }
+
+    #[test]
+    fn duplicated_child_is_placed_once() {
+        let (mut tree, parent, children) = tree_with_children(2);
+        let mut detached = Vec::new();
+
+        let order = [children[1], children[0], children[1]];
+        reconcile_children(&mut tree, parent, &order, &mut detached);
+
+        assert_eq!(tree.children(parent).unwrap(), [children[1], children[0]]);
+    }
+}
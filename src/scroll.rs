@@ -0,0 +1,316 @@
+/// Maximal count of passes in which scroll request waits for its node to be shown
+const MAX_SCROLL_REQUEST_PASSES: u8 = 4;
+
+/// Scroll state of scrollable node (`overflow: Scroll`) during the last frame
+/// (See [`crate::Tui::scroll_info`])
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollInfo {
+    /// Scroll offset of node content
+    pub offset: egui::Vec2,
+    /// Size of scrolled node content
+    pub content_size: egui::Vec2,
+    /// Size of visible node content area
+    pub viewport_size: egui::Vec2,
+    /// Viewport is at the end of node content in each dimension.
+    /// Pinned viewport follows the end of content if [`ScrollConfig::stick_to_bottom`]
+    /// or [`ScrollConfig::stick_to_right`] is enabled.
+    pub stuck_to_end: egui::Vec2b,
+}
+
+impl ScrollInfo {
+    pub(crate) fn new(
+        offset: egui::Vec2,
+        content_size: egui::Vec2,
+        viewport_size: egui::Vec2,
+    ) -> Self {
+        let mut info = Self {
+            offset,
+            content_size,
+            viewport_size,
+            stuck_to_end: egui::Vec2b::FALSE,
+        };
+        // Tolerate rounding of scroll offset
+        let max_offset = info.max_offset() - egui::Vec2::splat(0.5);
+        info.stuck_to_end = egui::Vec2b::new(offset.x >= max_offset.x, offset.y >= max_offset.y);
+        info
+    }
+
+    /// Maximal scroll offset in each dimension
+    #[inline]
+    pub fn max_offset(&self) -> egui::Vec2 {
+        (self.content_size - self.viewport_size).max(egui::Vec2::ZERO)
+    }
+}
+
+/// Scroll area settings of scrollable node (`overflow: Scroll`)
+/// (See [`crate::TuiBuilderLogic::scroll_config`]).
+///
+/// Unset options use [`egui::ScrollArea`] defaults.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollConfig {
+    /// When scroll bars are shown
+    pub scroll_bar_visibility: Option<egui::scroll_area::ScrollBarVisibility>,
+
+    /// Which input sources can scroll the node (scroll bar, content drag, mouse wheel)
+    pub scroll_source: Option<egui::scroll_area::ScrollSource>,
+
+    /// Shrink scroll area to the content size in each dimension
+    pub auto_shrink: Option<egui::Vec2b>,
+
+    /// Animate programmatic scrolling and scroll bar clicks
+    pub animated: Option<bool>,
+
+    /// Scroll area id salt.
+    ///
+    /// When set, scroll area id is derived from the salt and the parent node ui instead of
+    /// the node id, therefore scroll state is kept when node id changes (Node must stay
+    /// under the same parent). Salt must be unique among scrollable siblings.
+    pub id_salt: Option<egui::Id>,
+
+    /// Keep viewport at the end of node content in each dimension
+    /// while user has not scrolled away from it
+    pub stick_to_end: egui::Vec2b,
+}
+
+impl ScrollConfig {
+    /// Set when scroll bars are shown
+    pub fn scroll_bar_visibility(
+        mut self,
+        visibility: egui::scroll_area::ScrollBarVisibility,
+    ) -> Self {
+        self.scroll_bar_visibility = Some(visibility);
+        self
+    }
+
+    /// Set which input sources can scroll the node
+    pub fn scroll_source(mut self, scroll_source: egui::scroll_area::ScrollSource) -> Self {
+        self.scroll_source = Some(scroll_source);
+        self
+    }
+
+    /// Set if scroll area shrinks to the content size in each dimension
+    pub fn auto_shrink(mut self, auto_shrink: impl Into<egui::Vec2b>) -> Self {
+        self.auto_shrink = Some(auto_shrink.into());
+        self
+    }
+
+    /// Set if programmatic scrolling and scroll bar clicks are animated
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animated = Some(animated);
+        self
+    }
+
+    /// Set scroll area id salt
+    pub fn id_salt(mut self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_salt = Some(egui::Id::new(id_salt));
+        self
+    }
+
+    /// Keep viewport at the bottom of node content while user has not scrolled away from it.
+    ///
+    /// Useful for logs and chats. Viewport is moved to the new end of content as soon as
+    /// node layout is recalculated. See [`ScrollInfo::stuck_to_end`].
+    pub fn stick_to_bottom(mut self, stick: bool) -> Self {
+        self.stick_to_end.y = stick;
+        self
+    }
+
+    /// Keep viewport at the right side of node content while user has not scrolled away from it
+    /// (See [`ScrollConfig::stick_to_bottom`])
+    pub fn stick_to_right(mut self, stick: bool) -> Self {
+        self.stick_to_end.x = stick;
+        self
+    }
+
+    /// Apply settings to the scroll area
+    pub(crate) fn apply(&self, mut scroll: egui::ScrollArea) -> egui::ScrollArea {
+        if let Some(visibility) = self.scroll_bar_visibility {
+            scroll = scroll.scroll_bar_visibility(visibility);
+        }
+        if let Some(scroll_source) = self.scroll_source {
+            scroll = scroll.scroll_source(scroll_source);
+        }
+        if let Some(auto_shrink) = self.auto_shrink {
+            scroll = scroll.auto_shrink(auto_shrink);
+        }
+        if let Some(animated) = self.animated {
+            scroll = scroll.animated(animated);
+        }
+        if let Some(id_salt) = self.id_salt {
+            scroll = scroll.id_salt(id_salt);
+        }
+        // Sticking to the end is handled by Tui using taffy content size,
+        // egui scroll area would also override programmatically set scroll offset
+        scroll
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ScrollTarget {
+    /// Scroll node rectangle into view in the nearest enclosing scroll area
+    Node {
+        align: Option<egui::Align>,
+        /// `None` uses [`egui::Style::scroll_animation`]
+        animation: Option<egui::style::ScrollAnimation>,
+    },
+    /// Set scroll offset of scrollable node
+    Offset(egui::Vec2),
+}
+
+/// Pending scroll request of the node
+/// (See [`crate::Tui::scroll_to`] and [`crate::Tui::set_scroll_offset`])
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ScrollRequest {
+    target: ScrollTarget,
+    /// Count of passes in which request was not handled
+    passes: u8,
+}
+
+impl ScrollRequest {
+    pub(crate) fn node(
+        align: Option<egui::Align>,
+        animation: Option<egui::style::ScrollAnimation>,
+    ) -> Self {
+        Self {
+            target: ScrollTarget::Node { align, animation },
+            passes: 0,
+        }
+    }
+
+    pub(crate) fn offset(offset: egui::Vec2) -> Self {
+        Self {
+            target: ScrollTarget::Offset(offset),
+            passes: 0,
+        }
+    }
+
+    /// Requested scroll offset of scrollable node
+    #[inline]
+    pub(crate) fn scroll_offset(&self) -> Option<egui::Vec2> {
+        match self.target {
+            ScrollTarget::Offset(offset) => Some(offset),
+            ScrollTarget::Node { .. } => None,
+        }
+    }
+
+    /// Scroll given node rectangle into view in the nearest enclosing scroll area of `ui`.
+    ///
+    /// Returns false if request does not scroll node into view.
+    pub(crate) fn scroll_to_node(&self, ui: &egui::Ui, rect: egui::Rect) -> bool {
+        let ScrollTarget::Node { align, animation } = self.target else {
+            return false;
+        };
+        let animation = animation.unwrap_or(ui.style().scroll_animation);
+        ui.scroll_to_rect_animation(rect, align, animation);
+        true
+    }
+
+    /// Count pass in which request was not handled, returns false if request has expired
+    pub(crate) fn retain(&mut self) -> bool {
+        self.passes += 1;
+        self.passes <= MAX_SCROLL_REQUEST_PASSES
+    }
+}
+
+/// Offset of sticky node from its normal position in one dimension
+/// (CSS `position: sticky`, see [`crate::TuiBuilderLogic::position_sticky`]).
+///
+/// `start` and `end` are inset distances from the visible area edges,
+/// node is not moved outside of `bounds`.
+pub(crate) fn sticky_axis_offset(
+    node: egui::Rangef,
+    visible: egui::Rangef,
+    start: Option<f32>,
+    end: Option<f32>,
+    bounds: Option<egui::Rangef>,
+) -> f32 {
+    let mut offset: f32 = 0.;
+    if let Some(end) = end {
+        offset = offset.min(visible.max - end - node.max);
+    }
+    if let Some(start) = start {
+        // Start inset has priority over the end inset
+        offset = offset.max(visible.min + start - node.min);
+    }
+
+    match bounds {
+        Some(bounds) if offset > 0. => offset.min((bounds.max - node.max).max(0.)),
+        Some(bounds) if offset < 0. => offset.max((bounds.min - node.min).min(0.)),
+        _ => offset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(min: f32, max: f32) -> egui::Rangef {
+        egui::Rangef::new(min, max)
+    }
+
+    #[test]
+    fn sticky_node_is_not_moved_inside_of_visible_area() {
+        let visible = range(0., 100.);
+        let node = range(20., 40.);
+        assert_eq!(sticky_axis_offset(node, visible, Some(0.), None, None), 0.);
+        assert_eq!(sticky_axis_offset(node, visible, None, Some(0.), None), 0.);
+        assert_eq!(sticky_axis_offset(node, visible, None, None, None), 0.);
+    }
+
+    #[test]
+    fn sticky_node_sticks_to_inset_edges() {
+        let visible = range(0., 100.);
+        // Scrolled above the visible area
+        assert_eq!(
+            sticky_axis_offset(range(-50., -30.), visible, Some(10.), None, None),
+            60.
+        );
+        // Scrolled below the visible area
+        assert_eq!(
+            sticky_axis_offset(range(150., 170.), visible, None, Some(10.), None),
+            -80.
+        );
+        // Start inset has priority if node does not fit between both insets
+        assert_eq!(
+            sticky_axis_offset(range(-50., 50.), range(0., 60.), Some(0.), Some(0.), None),
+            50.
+        );
+    }
+
+    #[test]
+    fn sticky_node_stays_inside_of_bounds() {
+        let visible = range(0., 100.);
+        // Node is pushed out together with the end of its parent
+        assert_eq!(
+            sticky_axis_offset(
+                range(-50., -30.),
+                visible,
+                Some(0.),
+                None,
+                Some(range(-50., 10.))
+            ),
+            40.
+        );
+        assert_eq!(
+            sticky_axis_offset(
+                range(150., 170.),
+                visible,
+                None,
+                Some(0.),
+                Some(range(90., 200.))
+            ),
+            -60.
+        );
+        // Node at the end of its parent is not moved
+        assert_eq!(
+            sticky_axis_offset(
+                range(-20., 0.),
+                visible,
+                Some(0.),
+                None,
+                Some(range(-50., 0.))
+            ),
+            0.
+        );
+    }
+}
@@ -0,0 +1,438 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use taffy::Point;
+use taffy::prelude::*;
+
+use crate::TaffyState;
+
+/// Snapshot of computed egui_taffy layout tree.
+///
+/// Contains node ids, parent / child structure and computed layout of every node.
+/// Can be exported as pretty text ([`LayoutSnapshot::to_pretty_text`]) or
+/// JSON ([`LayoutSnapshot::to_json`]). Both formats are stable: the same layout
+/// always produces the same output, therefore snapshots can be stored as golden files.
+/// Nodes are identified by their child index path (See [`NodeSnapshot::path`]) and
+/// [`egui::Id`] (See [`NodeSnapshot::id`]) in exported formats. Id hashes are deterministic,
+/// they change only when node id or ids of its ancestors change.
+///
+/// ```ignore
+/// let snapshot = LayoutSnapshot::capture(ctx, taffy_id).unwrap();
+/// assert_eq!(snapshot.to_pretty_text(), include_str!("golden/screen.txt"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LayoutSnapshot {
+    /// Layout root node (None if layout has not been calculated yet)
+    pub root: Option<NodeSnapshot>,
+}
+
+/// Computed layout of single node in [`LayoutSnapshot`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSnapshot {
+    /// Node identifier, exported as 16 digit hexadecimal value of [`egui::Id::value`]
+    pub id: egui::Id,
+    /// Child index path from the root node: `/` is the root node,
+    /// `/0/2` is the third child of the first root child
+    pub path: String,
+    /// Position of the node relative to the parent node
+    pub location: Point<f32>,
+    /// Node size
+    pub size: Size<f32>,
+    /// Size of node content (Can be larger than node size if content overflows)
+    pub content_size: Size<f32>,
+    /// Node padding
+    pub padding: Rect<f32>,
+    /// Node border
+    pub border: Rect<f32>,
+    /// Node margin
+    pub margin: Rect<f32>,
+    /// Scroll offset of node content (Zero if node is not scrollable)
+    pub scroll_offset: egui::Vec2,
+    /// Is node position sticky in specified dimensions
+    pub sticky: egui::Vec2b,
+    /// Child nodes in layout order
+    pub children: Vec<NodeSnapshot>,
+}
+
+impl LayoutSnapshot {
+    /// Capture layout snapshot of egui_taffy instance with given id.
+    ///
+    /// Returns `None` if layout state does not exist or is currently in use.
+    pub fn capture(ctx: &egui::Context, taffy_id: impl Into<egui::Id>) -> Option<Self> {
+        TaffyState::with(ctx, taffy_id.into(), |state| state.layout_snapshot())
+    }
+
+    pub(crate) fn from_state(state: &TaffyState) -> Self {
+        let node_ids = state.node_egui_ids();
+
+        let mut nodes = Vec::new();
+        let root = state.root_node().and_then(|root| {
+            node_snapshot(state, node_ids, root, String::from("/"), &mut nodes);
+            nodes.pop()
+        });
+
+        Self { root }
+    }
+
+    /// Export snapshot as indented human readable text, one node per line
+    pub fn to_pretty_text(&self) -> String {
+        let mut out = String::new();
+        if let Some(root) = &self.root {
+            root.write_pretty_text(&mut out, 0);
+        }
+        out
+    }
+
+    /// Export snapshot as pretty printed JSON
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        match &self.root {
+            Some(root) => {
+                out.push_str("{\n  \"root\": ");
+                root.write_json(&mut out, 1);
+                out.push_str("\n}\n");
+            }
+            None => out.push_str("{\n  \"root\": null\n}\n"),
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for LayoutSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_pretty_text())
+    }
+}
+
+/// Build snapshot of the node and its descendants, result is pushed to `out`
+fn node_snapshot(
+    state: &TaffyState,
+    node_ids: &HashMap<NodeId, egui::Id>,
+    node_id: NodeId,
+    path: String,
+    out: &mut Vec<NodeSnapshot>,
+) {
+    let Some(&id) = node_ids.get(&node_id) else {
+        return;
+    };
+
+    let taffy_tree = state.taffy_tree();
+    let layout = taffy_tree.layout(node_id).unwrap();
+    let node_data = &state.items()[&id];
+
+    let mut children = Vec::new();
+    for (idx, child) in taffy_tree.child_ids(node_id).enumerate() {
+        let separator = if path.ends_with('/') { "" } else { "/" };
+        let child_path = format!("{path}{separator}{idx}");
+        node_snapshot(state, node_ids, child, child_path, &mut children);
+    }
+
+    out.push(NodeSnapshot {
+        id,
+        path,
+        location: layout.location,
+        size: layout.size,
+        content_size: layout.content_size,
+        padding: layout.padding,
+        border: layout.border,
+        margin: layout.margin,
+        scroll_offset: node_data.scroll_offset(),
+        sticky: node_data.sticky(),
+        children,
+    });
+}
+
+impl NodeSnapshot {
+    fn write_pretty_text(&self, out: &mut String, depth: usize) {
+        for _ in 0..depth {
+            out.push_str("  ");
+        }
+
+        let _ = write!(
+            out,
+            "{} id={} location=({}, {}) size=({} x {}) content_size=({} x {})",
+            self.path,
+            id_hex(self.id),
+            self.location.x,
+            self.location.y,
+            self.size.width,
+            self.size.height,
+            self.content_size.width,
+            self.content_size.height,
+        );
+        for (name, rect) in [
+            ("padding", &self.padding),
+            ("border", &self.border),
+            ("margin", &self.margin),
+        ] {
+            if *rect != Rect::ZERO {
+                let _ = write!(
+                    out,
+                    " {name}=[{} {} {} {}]",
+                    rect.left, rect.right, rect.top, rect.bottom
+                );
+            }
+        }
+        if self.scroll_offset != egui::Vec2::ZERO {
+            let _ = write!(
+                out,
+                " scroll_offset=({}, {})",
+                self.scroll_offset.x, self.scroll_offset.y
+            );
+        }
+        if self.sticky.any() {
+            let _ = write!(out, " sticky=({}, {})", self.sticky.x, self.sticky.y);
+        }
+        out.push('\n');
+
+        for child in &self.children {
+            child.write_pretty_text(out, depth + 1);
+        }
+    }
+
+    fn write_json(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let field_indent = "  ".repeat(depth + 1);
+
+        let fields = [
+            ("path", json_string(&self.path)),
+            // JSON numbers can not represent all 64 bit values exactly
+            ("id", json_string(&id_hex(self.id))),
+            (
+                "location",
+                json_object(&[("x", self.location.x), ("y", self.location.y)]),
+            ),
+            (
+                "size",
+                json_object(&[("width", self.size.width), ("height", self.size.height)]),
+            ),
+            (
+                "content_size",
+                json_object(&[
+                    ("width", self.content_size.width),
+                    ("height", self.content_size.height),
+                ]),
+            ),
+            ("padding", json_rect(&self.padding)),
+            ("border", json_rect(&self.border)),
+            ("margin", json_rect(&self.margin)),
+            (
+                "scroll_offset",
+                json_object(&[("x", self.scroll_offset.x), ("y", self.scroll_offset.y)]),
+            ),
+            (
+                "sticky",
+                format!("{{ \"x\": {}, \"y\": {} }}", self.sticky.x, self.sticky.y),
+            ),
+        ];
+
+        out.push_str("{\n");
+        for (name, value) in fields {
+            let _ = writeln!(out, "{field_indent}\"{name}\": {value},");
+        }
+
+        let _ = write!(out, "{field_indent}\"children\": [");
+        if self.children.is_empty() {
+            out.push(']');
+        } else {
+            for (idx, child) in self.children.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                out.push('\n');
+                out.push_str(&"  ".repeat(depth + 2));
+                child.write_json(out, depth + 2);
+            }
+            let _ = write!(out, "\n{field_indent}]");
+        }
+        let _ = write!(out, "\n{indent}}}");
+    }
+}
+
+/// Exported node id (See [`NodeSnapshot::id`])
+fn id_hex(id: egui::Id) -> String {
+    format!("{:016x}", id.value())
+}
+
+/// Quoted JSON string with escaped special characters
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for chr in value.chars() {
+        match chr {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            chr if chr.is_control() => {
+                let _ = write!(out, "\\u{:04x}", chr as u32);
+            }
+            chr => out.push(chr),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// JSON does not support NaN and infinite values, they are exported as null
+fn json_number(value: f32) -> String {
+    if value.is_finite() {
+        format!("{value}")
+    } else {
+        "null".to_owned()
+    }
+}
+
+fn json_object(fields: &[(&str, f32)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("\"{name}\": {}", json_number(*value)))
+        .collect();
+    format!("{{ {} }}", fields.join(", "))
+}
+
+fn json_rect(rect: &Rect<f32>) -> String {
+    json_object(&[
+        ("left", rect.left),
+        ("right", rect.right),
+        ("top", rect.top),
+        ("bottom", rect.bottom),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(path: &str, location: (f32, f32), size: (f32, f32)) -> NodeSnapshot {
+        NodeSnapshot {
+            id: egui::Id::new(path),
+            path: path.to_owned(),
+            location: Point {
+                x: location.0,
+                y: location.1,
+            },
+            size: Size {
+                width: size.0,
+                height: size.1,
+            },
+            content_size: Size {
+                width: size.0,
+                height: size.1,
+            },
+            padding: Rect::ZERO,
+            border: Rect::ZERO,
+            margin: Rect::ZERO,
+            scroll_offset: egui::Vec2::ZERO,
+            sticky: egui::Vec2b::FALSE,
+            children: Vec::new(),
+        }
+    }
+
+    fn snapshot() -> LayoutSnapshot {
+        let mut root = node("/", (0., 0.), (100., 50.));
+        let mut scrolled = node("/0", (0., 0.), (100., 20.));
+        scrolled.padding = Rect {
+            left: 1.,
+            right: 2.,
+            top: 3.,
+            bottom: 4.,
+        };
+        scrolled.content_size.height = f32::NAN;
+        scrolled.scroll_offset = egui::vec2(0., 5.);
+        let mut sticky = node("/1", (0., 20.), (100., 30.));
+        sticky.sticky = egui::Vec2b::new(false, true);
+        root.children = vec![scrolled, sticky];
+        LayoutSnapshot { root: Some(root) }
+    }
+
+    /// Exported id of the test node with given path
+    fn id(path: &str) -> String {
+        id_hex(egui::Id::new(path))
+    }
+
+    #[test]
+    fn pretty_text_format() {
+        assert_eq!(
+            snapshot().to_pretty_text(),
+            format!(
+                "/ id={} location=(0, 0) size=(100 x 50) content_size=(100 x 50)\n\
+                \x20 /0 id={} location=(0, 0) size=(100 x 20) content_size=(100 x NaN) \
+                padding=[1 2 3 4] scroll_offset=(0, 5)\n\
+                \x20 /1 id={} location=(0, 20) size=(100 x 30) content_size=(100 x 30) \
+                sticky=(false, true)\n",
+                id("/"),
+                id("/0"),
+                id("/1"),
+            )
+        );
+    }
+
+    #[test]
+    fn json_format() {
+        let empty_rect = "{ \"left\": 0, \"right\": 0, \"top\": 0, \"bottom\": 0 }";
+        let leaf = |indent: &str, path: &str, y: f32, height: f32| {
+            let id = id(path);
+            format!(
+                "{{\n\
+                {indent}  \"path\": \"{path}\",\n\
+                {indent}  \"id\": \"{id}\",\n\
+                {indent}  \"location\": {{ \"x\": 0, \"y\": {y} }},\n\
+                {indent}  \"size\": {{ \"width\": 100, \"height\": {height} }},\n\
+                {indent}  \"content_size\": {{ \"width\": 100, \"height\": {height} }},\n\
+                {indent}  \"padding\": {empty_rect},\n\
+                {indent}  \"border\": {empty_rect},\n\
+                {indent}  \"margin\": {empty_rect},\n\
+                {indent}  \"scroll_offset\": {{ \"x\": 0, \"y\": 0 }},\n\
+                {indent}  \"sticky\": {{ \"x\": false, \"y\": true }},\n\
+                {indent}  \"children\": []\n\
+                {indent}}}"
+            )
+        };
+
+        let mut snapshot = snapshot();
+        let root = snapshot.root.as_mut().unwrap();
+        root.children.remove(0);
+        let child = leaf("      ", "/1", 20., 30.);
+        let root_id = id("/");
+
+        assert_eq!(
+            snapshot.to_json(),
+            format!(
+                "{{\n  \"root\": {{\n\
+                \x20   \"path\": \"/\",\n\
+                \x20   \"id\": \"{root_id}\",\n\
+                \x20   \"location\": {{ \"x\": 0, \"y\": 0 }},\n\
+                \x20   \"size\": {{ \"width\": 100, \"height\": 50 }},\n\
+                \x20   \"content_size\": {{ \"width\": 100, \"height\": 50 }},\n\
+                \x20   \"padding\": {empty_rect},\n\
+                \x20   \"border\": {empty_rect},\n\
+                \x20   \"margin\": {empty_rect},\n\
+                \x20   \"scroll_offset\": {{ \"x\": 0, \"y\": 0 }},\n\
+                \x20   \"sticky\": {{ \"x\": false, \"y\": false }},\n\
+                \x20   \"children\": [\n\
+                \x20     {child}\n\
+                \x20   ]\n\
+                \x20 }}\n}}\n"
+            )
+        );
+        assert_eq!(
+            LayoutSnapshot::default().to_json(),
+            "{\n  \"root\": null\n}\n"
+        );
+    }
+
+    #[test]
+    fn json_values_are_escaped() {
+        assert_eq!(json_string("/0/1"), "\"/0/1\"");
+        assert_eq!(
+            json_string("quote\" backslash\\ newline\n tab\t bell\u{7}"),
+            "\"quote\\\" backslash\\\\ newline\\n tab\\t bell\\u0007\""
+        );
+        assert_eq!(json_number(f32::NAN), "null");
+        assert_eq!(json_number(f32::INFINITY), "null");
+        assert_eq!(json_number(1.5), "1.5");
+        assert_eq!(id_hex(egui::Id::new("/")).len(), 16);
+    }
+}
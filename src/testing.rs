@@ -0,0 +1,480 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::snapshot::{LayoutSnapshot, NodeSnapshot};
+use crate::{TaffyState, Tui, TuiId, tui};
+
+/// Headless test harness that runs [`Tui`] layout without GPU or windowing backend.
+///
+/// Harness drives [`egui::Context`] at the given screen size and pixels per point until
+/// layout converges and returns calculated node rectangles.
+///
+/// ```
+/// use egui_taffy::taffy::{self, prelude::*};
+/// use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+///
+/// let mut harness = TuiTestHarness::new(egui::vec2(1280., 720.)).style(taffy::Style {
+///     size: percent(1.),
+///     ..Default::default()
+/// });
+///
+/// let result = harness.run(|tui| {
+///     tui.id("sidebar")
+///         .style(taffy::Style {
+///             size: taffy::Size {
+///                 width: length(240.),
+///                 height: percent(1.),
+///             },
+///             ..Default::default()
+///         })
+///         .add(|tui| tui.label("Sidebar"));
+///
+///     tui.id("content")
+///         .style(taffy::Style {
+///             flex_grow: 1.,
+///             ..Default::default()
+///         })
+///         .add(|tui| tui.label("Content"));
+/// });
+///
+/// assert!(result.converged());
+/// assert_eq!(result.rect("sidebar").unwrap().width(), 240.);
+/// assert_eq!(result.rect("content").unwrap().width(), 1280. - 240.);
+/// ```
+pub struct TuiTestHarness {
+    ctx: egui::Context,
+    id: egui::Id,
+    screen_size: egui::Vec2,
+    pixels_per_point: f32,
+    max_frames: usize,
+    style: taffy::Style,
+    /// Input events queued for the following frames (One entry per frame)
+    input: VecDeque<Vec<egui::Event>>,
+    pointer_pos: Option<egui::Pos2>,
+    /// Rectangles in which nodes were shown during the last frame
+    shown_rects: HashMap<egui::Id, egui::Rect>,
+    last_result: Option<TuiTestResult>,
+}
+
+impl TuiTestHarness {
+    /// Create harness with given screen size in physical pixels
+    pub fn new(screen_size: egui::Vec2) -> Self {
+        let ctx = egui::Context::default();
+        // Layout changes are applied in the same frame (View README for more details)
+        ctx.options_mut(|options| {
+            options.max_passes = std::num::NonZeroUsize::new(3).unwrap();
+        });
+
+        Self {
+            ctx,
+            id: egui::Id::new("egui_taffy_test_harness"),
+            screen_size,
+            pixels_per_point: 1.,
+            max_frames: 10,
+            style: Default::default(),
+            input: VecDeque::new(),
+            pointer_pos: None,
+            shown_rects: HashMap::new(),
+            last_result: None,
+        }
+    }
+
+    /// Set pixels per point (Screen scale factor). Default: 1.
+    pub fn pixels_per_point(mut self, pixels_per_point: f32) -> Self {
+        self.pixels_per_point = pixels_per_point;
+        self
+    }
+
+    /// Maximal number of frames to wait for layout convergence. Default: 10
+    pub fn max_frames(mut self, max_frames: usize) -> Self {
+        self.max_frames = max_frames.max(1);
+        self
+    }
+
+    /// Set root node style
+    pub fn style(mut self, style: taffy::Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Id of tested Tui instance (Can be used with [`crate::TaffyState::with`])
+    pub fn id(&self) -> egui::Id {
+        self.id
+    }
+
+    /// Egui context used by harness
+    pub fn ctx(&self) -> &egui::Context {
+        &self.ctx
+    }
+
+    /// Screen rectangle in points
+    pub fn screen_rect(&self) -> egui::Rect {
+        egui::Rect::from_min_size(egui::Pos2::ZERO, self.screen_size / self.pixels_per_point)
+    }
+
+    /// Run frames until queued input is processed and layout converges
+    /// or [`TuiTestHarness::max_frames`] is reached.
+    ///
+    /// Layout has converged when frame does not request pass discard
+    /// and calculated layout matches the previous frame.
+    pub fn run(&mut self, mut f: impl FnMut(&mut Tui)) -> TuiTestResult {
+        let mut previous: Option<LayoutSnapshot> = None;
+        let mut frames = 0;
+        let mut settle_frames = 0;
+
+        loop {
+            let has_input = !self.input.is_empty();
+            let (snapshot, discarded) = self.run_frame(&mut f);
+            frames += 1;
+
+            if !has_input {
+                settle_frames += 1;
+                let converged = !discarded && previous.as_ref() == Some(&snapshot);
+                if converged || settle_frames >= self.max_frames {
+                    return self.finish(snapshot, frames, converged);
+                }
+            }
+            previous = Some(snapshot);
+        }
+    }
+
+    /// Run exactly given number of frames and return the layout of the last frame
+    pub fn run_frames(&mut self, frames: usize, mut f: impl FnMut(&mut Tui)) -> TuiTestResult {
+        let mut previous: Option<LayoutSnapshot> = None;
+        let mut converged = false;
+
+        for _ in 0..frames {
+            let (snapshot, discarded) = self.run_frame(&mut f);
+            converged = !discarded && previous.as_ref() == Some(&snapshot);
+            previous = Some(snapshot);
+        }
+
+        self.finish(previous.unwrap_or_default(), frames, converged)
+    }
+
+    /// Layout of the last run
+    pub fn last_result(&self) -> Option<&TuiTestResult> {
+        self.last_result.as_ref()
+    }
+
+    fn finish(
+        &mut self,
+        snapshot: LayoutSnapshot,
+        frames: usize,
+        converged: bool,
+    ) -> TuiTestResult {
+        let result = TuiTestResult::new(
+            self.id,
+            self.screen_rect(),
+            snapshot,
+            &self.shown_rects,
+            frames,
+            converged,
+        );
+        self.last_result = Some(result.clone());
+        result
+    }
+
+    /// Run single egui frame, returns layout and if any pass of the frame was discarded
+    fn run_frame(&mut self, f: &mut impl FnMut(&mut Tui)) -> (LayoutSnapshot, bool) {
+        let mut input = egui::RawInput {
+            screen_rect: Some(self.screen_rect()),
+            events: self.input.pop_front().unwrap_or_default(),
+            ..Default::default()
+        };
+        input
+            .viewports
+            .entry(egui::ViewportId::ROOT)
+            .or_default()
+            .native_pixels_per_point = Some(self.pixels_per_point);
+
+        let id = self.id;
+        let style = &self.style;
+        let output = self.ctx.run(input, |ctx| {
+            egui::CentralPanel::default()
+                .frame(egui::Frame::NONE)
+                .show(ctx, |ui| {
+                    tui(ui, id)
+                        .reserve_available_space()
+                        .style(style.clone())
+                        .show(|tui| f(tui));
+                });
+        });
+
+        let snapshot = LayoutSnapshot::capture(&self.ctx, id).unwrap_or_default();
+        let shown_rects = &mut self.shown_rects;
+        shown_rects.clear();
+        TaffyState::with(&self.ctx, id, |state| {
+            // Nodes that were not shown in the last frame keep their old rectangle
+            let generation = state.generation();
+            shown_rects.extend(
+                state
+                    .items()
+                    .iter()
+                    .filter(|(_, node_data)| node_data.generation == generation)
+                    .map(|(id, node_data)| (*id, node_data.rect())),
+            );
+        });
+        (snapshot, output.platform_output.requested_discard())
+    }
+}
+
+/// Simulated input.
+///
+/// Input is queued and applied during the following [`TuiTestHarness::run`].
+/// Nodes are located using the layout of the last run, interactions panic if node is not found.
+///
+/// Responses (For example, [`crate::TuiInnerResponse`] returned by `button`) are read back
+/// inside the layout closure. Input events are delivered only to the first pass of the frame.
+///
+/// ```
+/// use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+///
+/// let mut clicks = 0;
+/// let mut layout = |tui: &mut egui_taffy::Tui| {
+///     if tui.id("ok").button(|tui| tui.label("Ok")).clicked() {
+///         clicks += 1;
+///     }
+/// };
+///
+/// let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+/// harness.run(&mut layout);
+/// harness.click("ok");
+/// harness.run(&mut layout);
+///
+/// assert_eq!(clicks, 1);
+/// ```
+impl TuiTestHarness {
+    /// Queue input events for a single frame
+    pub fn queue_events(&mut self, events: Vec<egui::Event>) -> &mut Self {
+        self.input.push_back(events);
+        self
+    }
+
+    /// Move pointer to the given position
+    pub fn hover_at(&mut self, pos: egui::Pos2) -> &mut Self {
+        self.pointer_pos = Some(pos);
+        self.queue_events(vec![egui::Event::PointerMoved(pos)])
+    }
+
+    /// Move pointer to the centre of the node
+    pub fn hover(&mut self, id: impl Into<TuiId>) -> &mut Self {
+        let pos = self.node_center(id);
+        self.hover_at(pos)
+    }
+
+    /// Click with primary pointer button at the given position
+    pub fn click_at(&mut self, pos: egui::Pos2) -> &mut Self {
+        self.hover_at(pos);
+        self.input
+            .back_mut()
+            .unwrap()
+            .push(pointer_button(pos, true));
+        self.queue_events(vec![pointer_button(pos, false)])
+    }
+
+    /// Click with primary pointer button at the centre of the node
+    pub fn click(&mut self, id: impl Into<TuiId>) -> &mut Self {
+        let pos = self.node_center(id);
+        self.click_at(pos)
+    }
+
+    /// Drag with primary pointer button from the centre of the node by given delta
+    pub fn drag(&mut self, id: impl Into<TuiId>, delta: egui::Vec2) -> &mut Self {
+        let from = self.node_center(id);
+        let to = from + delta;
+
+        self.hover_at(from);
+        self.input
+            .back_mut()
+            .unwrap()
+            .push(pointer_button(from, true));
+        self.hover_at(to);
+        self.queue_events(vec![pointer_button(to, false)])
+    }
+
+    /// Scroll content under the centre of the node (For example, `overflow: Scroll` node).
+    ///
+    /// Positive delta moves content right and down, same as [`egui::Event::MouseWheel`].
+    pub fn scroll(&mut self, id: impl Into<TuiId>, delta: egui::Vec2) -> &mut Self {
+        let pos = self.node_center(id);
+        self.hover_at(pos);
+        self.input
+            .back_mut()
+            .unwrap()
+            .push(egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Point,
+                delta,
+                modifiers: egui::Modifiers::NONE,
+            });
+        self
+    }
+
+    /// Press and release keyboard key
+    pub fn key_press(&mut self, key: egui::Key, modifiers: egui::Modifiers) -> &mut Self {
+        let key_event = |pressed| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed,
+            repeat: false,
+            modifiers,
+        };
+        self.queue_events(vec![key_event(true)]);
+        self.queue_events(vec![key_event(false)])
+    }
+
+    /// Type text into focused widget
+    pub fn type_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.queue_events(vec![egui::Event::Text(text.into())])
+    }
+
+    /// Last pointer position set by simulated input
+    pub fn pointer_pos(&self) -> Option<egui::Pos2> {
+        self.pointer_pos
+    }
+
+    /// Resolved node [`egui::Id`] in the layout of the last run that can be used with
+    /// [`crate::TaffyState`] methods (See [`TuiTestResult::node_id`]).
+    ///
+    /// Panics if node is not found.
+    pub fn node_id(&self, id: impl Into<TuiId>) -> egui::Id {
+        let id = id.into();
+        self.last_result
+            .as_ref()
+            .and_then(|result| result.node_id(id.clone()))
+            .unwrap_or_else(|| panic!("Node {id:?} not found in the layout of the last run"))
+    }
+
+    fn node_center(&self, id: impl Into<TuiId>) -> egui::Pos2 {
+        let id = id.into();
+        self.last_result
+            .as_ref()
+            .and_then(|result| result.rect(id.clone()))
+            .unwrap_or_else(|| panic!("Node {id:?} not found in the layout of the last run"))
+            .center()
+    }
+}
+
+fn pointer_button(pos: egui::Pos2, pressed: bool) -> egui::Event {
+    egui::Event::PointerButton {
+        pos,
+        button: egui::PointerButton::Primary,
+        pressed,
+        modifiers: egui::Modifiers::NONE,
+    }
+}
+
+/// Layout calculated by [`TuiTestHarness`]
+#[derive(Clone)]
+pub struct TuiTestResult {
+    snapshot: LayoutSnapshot,
+    frames: usize,
+    converged: bool,
+    nodes: HashMap<egui::Id, TestNode>,
+}
+
+/// Calculated node information in test result
+#[derive(Clone)]
+struct TestNode {
+    parent: egui::Id,
+    rect: egui::Rect,
+}
+
+impl TuiTestResult {
+    fn new(
+        id: egui::Id,
+        screen_rect: egui::Rect,
+        snapshot: LayoutSnapshot,
+        shown_rects: &HashMap<egui::Id, egui::Rect>,
+        frames: usize,
+        converged: bool,
+    ) -> Self {
+        let mut nodes = HashMap::new();
+        if let Some(root) = &snapshot.root {
+            collect_nodes(&mut nodes, shown_rects, root, id, screen_rect.min);
+        }
+
+        Self {
+            snapshot,
+            frames,
+            converged,
+            nodes,
+        }
+    }
+
+    /// Did layout converge
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// Number of frames that were run
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Snapshot of calculated layout
+    pub fn snapshot(&self) -> &LayoutSnapshot {
+        &self.snapshot
+    }
+
+    /// Node rectangle (border box) in screen points.
+    ///
+    /// Rectangle of shown node includes layout animation and sticky offsets,
+    /// rectangle of node which content was skipped is calculated from its last layout.
+    ///
+    /// [`TuiId::Hiarchy`] ids are matched against direct children of every node,
+    /// first match in layout order is returned.
+    /// [`TuiId::Auto`] ids can not be resolved outside of the layout, use
+    /// [`TuiTestResult::rect_by_egui_id`] instead.
+    pub fn rect(&self, id: impl Into<TuiId>) -> Option<egui::Rect> {
+        self.node_id(id).and_then(|id| self.rect_by_egui_id(id))
+    }
+
+    /// Resolved node [`egui::Id`] that can be used with [`crate::TaffyState`] methods.
+    ///
+    /// Ids are matched the same way as in [`TuiTestResult::rect`].
+    pub fn node_id(&self, id: impl Into<TuiId>) -> Option<egui::Id> {
+        match id.into() {
+            TuiId::Unique(id) => self.nodes.contains_key(&id).then_some(id),
+            TuiId::Hiarchy(id) => self.find_hiarchy(id),
+            TuiId::Auto => None,
+        }
+    }
+
+    /// Node rectangle (border box) in screen points by resolved node [`egui::Id`]
+    pub fn rect_by_egui_id(&self, id: egui::Id) -> Option<egui::Rect> {
+        self.nodes.get(&id).map(|node| node.rect)
+    }
+
+    fn find_hiarchy(&self, id: egui::Id) -> Option<egui::Id> {
+        let mut stack: Vec<&NodeSnapshot> = self.snapshot.root.iter().collect();
+        while let Some(node) = stack.pop() {
+            if self.nodes[&node.id].parent.with(id) == node.id {
+                return Some(node.id);
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        None
+    }
+}
+
+/// Calculate absolute rectangles of the node and its descendants.
+/// Rectangles of shown nodes are taken from `shown_rects`.
+fn collect_nodes(
+    nodes: &mut HashMap<egui::Id, TestNode>,
+    shown_rects: &HashMap<egui::Id, egui::Rect>,
+    node: &NodeSnapshot,
+    parent: egui::Id,
+    origin: egui::Pos2,
+) {
+    let rect = shown_rects.get(&node.id).copied().unwrap_or_else(|| {
+        let min = origin + egui::vec2(node.location.x, node.location.y);
+        egui::Rect::from_min_size(min, egui::vec2(node.size.width, node.size.height))
+    });
+    nodes.insert(node.id, TestNode { parent, rect });
+
+    // Children are placed relative to parent border box and moved by scroll offset
+    let child_origin = rect.min - node.scroll_offset;
+    for child in &node.children {
+        collect_nodes(nodes, shown_rects, child, node.id, child_origin);
+    }
+}
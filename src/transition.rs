@@ -0,0 +1,250 @@
+use egui::epaint::ClippedShape;
+use taffy::prelude::*;
+
+/// Enter and exit transition settings for conditionally shown nodes.
+///
+/// New nodes fade and/or grow in. Removed nodes keep their last layout slot
+/// and fade and/or collapse out before they are removed from the layout.
+/// Nodes are matched between frames by their [`crate::TuiId`].
+///
+/// Removed node content is not drawn by application anymore, therefore last painted
+/// shapes of the node are replayed during exit transition.
+#[derive(Clone, Copy, Debug)]
+pub struct NodeTransition {
+    /// Transition duration in seconds
+    pub duration: f32,
+
+    /// Easing function that maps linear transition progress `0..=1` to eased progress.
+    /// See [`egui::emath::easing`] for available functions.
+    pub easing: fn(f32) -> f32,
+
+    /// Fade node in and out
+    pub fade: bool,
+
+    /// Grow node from zero size and collapse it to zero size in specified dimensions
+    pub collapse: egui::Vec2b,
+}
+
+impl Default for NodeTransition {
+    fn default() -> Self {
+        Self {
+            duration: 0.2,
+            easing: egui::emath::easing::cubic_out,
+            fade: true,
+            collapse: egui::Vec2b::FALSE,
+        }
+    }
+}
+
+impl NodeTransition {
+    /// Create transition with given duration in seconds
+    pub fn new(duration: f32) -> Self {
+        Self {
+            duration,
+            ..Default::default()
+        }
+    }
+
+    /// Set easing function
+    pub fn easing(mut self, easing: fn(f32) -> f32) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Fade node in and out
+    pub fn fade(mut self, fade: bool) -> Self {
+        self.fade = fade;
+        self
+    }
+
+    /// Grow and collapse node size in specified dimensions
+    pub fn collapse(mut self, collapse: egui::Vec2b) -> Self {
+        self.collapse = collapse;
+        self
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum TransitionPhase {
+    Enter,
+    Shown,
+    Exit,
+}
+
+/// Transition state of a single node
+pub(crate) struct TransitionState {
+    /// Node id, used to keep exiting node alive
+    pub id: egui::Id,
+    pub config: NodeTransition,
+    phase: TransitionPhase,
+    start_time: f64,
+    /// Node size without transition
+    natural_size: Option<egui::Vec2>,
+    /// Last painted node content and node rectangle at the time of painting
+    shapes: Vec<ClippedShape>,
+    shapes_rect: egui::Rect,
+}
+
+impl TransitionState {
+    pub(crate) fn new(
+        id: egui::Id,
+        config: NodeTransition,
+        phase: TransitionPhase,
+        time: f64,
+    ) -> Self {
+        Self {
+            id,
+            config,
+            phase,
+            start_time: time,
+            natural_size: None,
+            shapes: Vec::new(),
+            shapes_rect: egui::Rect::NAN,
+        }
+    }
+
+    /// Linear progress of the current phase
+    fn progress(&self, time: f64) -> f32 {
+        if self.config.duration > 0. {
+            ((time - self.start_time) as f32 / self.config.duration).clamp(0., 1.)
+        } else {
+            1.
+        }
+    }
+
+    /// Update state of node that is shown in the current frame.
+    ///
+    /// Returns node visibility in range `0..=1`.
+    pub(crate) fn update_shown(&mut self, time: f64, layout_size: egui::Vec2) -> f32 {
+        if self.phase == TransitionPhase::Exit {
+            // Node was shown again, reverse exit transition
+            let progress = self.progress(time);
+            self.phase = TransitionPhase::Enter;
+            self.start_time = time - ((1. - progress) * self.config.duration) as f64;
+        }
+
+        let layout_size = layout_size.is_finite().then_some(layout_size);
+        match self.phase {
+            TransitionPhase::Enter => {
+                if self.natural_size.is_none() {
+                    // Layout was calculated without transition restrictions
+                    self.natural_size = layout_size;
+                }
+
+                let progress = self.progress(time);
+                if progress >= 1. {
+                    self.phase = TransitionPhase::Shown;
+                    1.
+                } else {
+                    (self.config.easing)(progress)
+                }
+            }
+            TransitionPhase::Shown | TransitionPhase::Exit => {
+                if layout_size.is_some() {
+                    self.natural_size = layout_size;
+                }
+                1.
+            }
+        }
+    }
+
+    /// Update state of node that was not shown in the current frame.
+    ///
+    /// Returns node visibility or `None` if exit transition has finished.
+    pub(crate) fn update_hidden(&mut self, time: f64) -> Option<f32> {
+        if self.phase != TransitionPhase::Exit {
+            // Continue from the currently shown state if enter transition is not finished
+            let progress = match self.phase {
+                TransitionPhase::Enter => self.progress(time),
+                _ => 1.,
+            };
+            self.phase = TransitionPhase::Exit;
+            self.start_time = time - ((1. - progress) * self.config.duration) as f64;
+        }
+
+        let progress = self.progress(time);
+        (progress < 1.).then(|| 1. - (self.config.easing)(progress))
+    }
+
+    /// Opacity of the node content with given visibility
+    pub(crate) fn opacity(&self, visibility: f32) -> f32 {
+        if self.config.fade { visibility } else { 1. }
+    }
+
+    /// Restrict node size in collapsed dimensions
+    pub(crate) fn apply_collapse(&self, style: &mut Style, visibility: f32) {
+        let Some(natural_size) = self.natural_size else {
+            return;
+        };
+        if visibility >= 1. {
+            return;
+        }
+
+        style.box_sizing = BoxSizing::BorderBox;
+        if self.config.collapse.x {
+            style.size.width = length(natural_size.x * visibility);
+            style.min_size.width = length(0.);
+            if style.overflow.x == taffy::Overflow::Visible {
+                style.overflow.x = taffy::Overflow::Clip;
+            }
+        }
+        if self.config.collapse.y {
+            style.size.height = length(natural_size.y * visibility);
+            style.min_size.height = length(0.);
+            if style.overflow.y == taffy::Overflow::Visible {
+                style.overflow.y = taffy::Overflow::Clip;
+            }
+        }
+    }
+
+    /// Style of exiting node. Node content is not placed anymore,
+    /// therefore node keeps its last size in dimensions that are not collapsed.
+    pub(crate) fn apply_exit_style(&self, style: &mut Style, visibility: f32) {
+        let Some(natural_size) = self.natural_size else {
+            return;
+        };
+
+        style.box_sizing = BoxSizing::BorderBox;
+        style.size = Size {
+            width: length(natural_size.x),
+            height: length(natural_size.y),
+        };
+        self.apply_collapse(style, visibility);
+    }
+
+    /// Store painted node content
+    pub(crate) fn store_shapes(&mut self, shapes: Vec<ClippedShape>, rect: egui::Rect) {
+        self.shapes = shapes;
+        self.shapes_rect = rect;
+    }
+
+    /// Paint last painted node content at the given node rectangle
+    pub(crate) fn paint_exit(&self, ui: &egui::Ui, rect: egui::Rect, visibility: f32) {
+        if self.shapes.is_empty() || self.shapes_rect.any_nan() || rect.any_nan() {
+            return;
+        }
+
+        let delta = rect.min - self.shapes_rect.min;
+        let mut painter = ui.painter().clone();
+        painter.multiply_opacity(self.opacity(visibility));
+
+        let clip_rect = if self.config.collapse.any() {
+            ui.clip_rect().intersect(rect)
+        } else {
+            ui.clip_rect()
+        };
+
+        for clipped_shape in &self.shapes {
+            let mut shape = clipped_shape.shape.clone();
+            shape.translate(delta);
+            painter
+                .with_clip_rect(
+                    clipped_shape
+                        .clip_rect
+                        .translate(delta)
+                        .intersect(clip_rect),
+                )
+                .add(shape);
+        }
+    }
+}
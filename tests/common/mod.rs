@@ -0,0 +1,38 @@
+//! Fixtures shared by integration tests
+#![allow(dead_code)]
+
+use egui_taffy::taffy::{self, prelude::*};
+
+/// Screen size of frames run by [`frame`]
+pub const SCREEN_SIZE: egui::Vec2 = egui::vec2(800., 600.);
+
+/// Fixed size node that does not shrink
+pub fn fixed(width: f32, height: f32) -> taffy::Style {
+    taffy::Style {
+        size: taffy::Size {
+            width: length(width),
+            height: length(height),
+        },
+        flex_shrink: 0.,
+        ..Default::default()
+    }
+}
+
+/// Run single frame with given input events, `add_contents` is shown in the central panel.
+///
+/// Use it to test Tui configurations that are not supported by
+/// [`egui_taffy::testing::TuiTestHarness`].
+pub fn frame(
+    ctx: &egui::Context,
+    events: Vec<egui::Event>,
+    mut add_contents: impl FnMut(&mut egui::Ui),
+) -> egui::FullOutput {
+    let input = egui::RawInput {
+        screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, SCREEN_SIZE)),
+        events,
+        ..Default::default()
+    };
+    ctx.run(input, |ctx| {
+        egui::CentralPanel::default().show(ctx, &mut add_contents);
+    })
+}
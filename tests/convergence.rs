@@ -0,0 +1,89 @@
+mod common;
+
+use egui_taffy::convergence::{ConvergenceReport, NodeChangeKind};
+use egui_taffy::{TaffyState, Tui, TuiBuilderLogic, tui};
+
+const TUI_ID: &str = "two_phase";
+
+fn frame(ctx: &egui::Context, mut f: impl FnMut(&mut Tui)) -> ConvergenceReport {
+    common::frame(ctx, Vec::new(), |ui| {
+        tui(ui, TUI_ID)
+            .reserve_available_space()
+            .show_two_phase(&mut f);
+    });
+    TaffyState::with(ctx, egui::Id::new(TUI_ID), |state| {
+        state.convergence_report().clone()
+    })
+    .unwrap()
+}
+
+fn layout(tui: &mut Tui) {
+    tui.label("First label");
+    tui.label("Second label");
+}
+
+#[test]
+fn measure_phase_is_included_in_report() {
+    let ctx = egui::Context::default();
+
+    let report = frame(&ctx, layout);
+    // Layout is recalculated in the measure phase and shown settled in the first pass
+    assert_eq!(report.frame_nr, 0);
+    assert_eq!(report.passes, 1);
+    assert!(report.recalculations > 0);
+    assert!(report.converged);
+    assert!(
+        report
+            .changes
+            .iter()
+            .any(|change| change.kind == NodeChangeKind::Added)
+    );
+
+    let report = frame(&ctx, layout);
+    assert_eq!(report.frame_nr, 1);
+    assert_eq!(report.recalculations, 0);
+    assert!(report.changes.is_empty());
+}
+
+/// Left edge of the second label in each frame, labels are placed in a row
+fn second_label_left(two_phase: bool, frames: usize) -> Vec<f32> {
+    let ctx = egui::Context::default();
+    ctx.options_mut(|options| {
+        // Frame is not rerun to fix the layout
+        options.max_passes = std::num::NonZeroUsize::new(1).unwrap();
+    });
+
+    (0..frames)
+        .map(|_| {
+            let mut left = None;
+            let mut layout = |tui: &mut Tui| {
+                tui.label("First label");
+                left = Some(tui.label("Second label").rect.left());
+            };
+            common::frame(&ctx, Vec::new(), |ui| {
+                let tui = tui(ui, TUI_ID).reserve_available_space();
+                if two_phase {
+                    tui.show_two_phase(&mut layout);
+                } else {
+                    tui.show(&mut layout);
+                }
+            });
+            left.unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn two_phase_places_content_in_the_first_frame() {
+    let shown = second_label_left(false, 3);
+    let two_phase = second_label_left(true, 3);
+
+    // Settled layout is the same in both modes
+    assert_eq!(shown[2], two_phase[2]);
+    assert!(two_phase[2] > shown[0]);
+
+    // First label is not measured yet when content is shown in the same pass
+    assert_ne!(shown[0], shown[2]);
+    // Measure phase lays out measured content before it is painted
+    assert_eq!(two_phase, [two_phase[2]; 3]);
+}
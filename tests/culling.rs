@@ -0,0 +1,63 @@
+use std::cell::RefCell;
+
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TaffyState, TuiBuilderLogic, testing::TuiTestHarness};
+
+/// Scrollable list of culled sections, shown section indices are stored in `shown`
+fn sections(shown: &RefCell<Vec<usize>>) -> impl FnMut(&mut egui_taffy::Tui) + '_ {
+    move |tui| {
+        shown.borrow_mut().clear();
+        tui.id("list")
+            .style(taffy::Style {
+                flex_direction: taffy::FlexDirection::Column,
+                overflow: taffy::Point {
+                    x: taffy::Overflow::Visible,
+                    y: taffy::Overflow::Scroll,
+                },
+                size: taffy::Size {
+                    width: length(200.),
+                    height: length(100.),
+                },
+                ..Default::default()
+            })
+            .add(|tui| {
+                for section in 0..10 {
+                    tui.id(egui::Id::new(section))
+                        .style(taffy::Style {
+                            flex_shrink: 0.,
+                            ..Default::default()
+                        })
+                        .add_culled(|tui| {
+                            shown.borrow_mut().push(section);
+                            tui.id("content")
+                                .style(taffy::Style {
+                                    size: taffy::Size {
+                                        width: length(200.),
+                                        height: length(60.),
+                                    },
+                                    ..Default::default()
+                                })
+                                .add_empty();
+                        });
+                }
+            });
+    }
+}
+
+#[test]
+fn offscreen_nodes_are_culled() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let shown = RefCell::new(Vec::new());
+
+    let result = harness.run(sections(&shown));
+    assert_eq!(*shown.borrow(), [0, 1]);
+    // Culled sections keep their size
+    assert_eq!(result.rect(egui::Id::new(9)).unwrap().top(), 9. * 60.);
+
+    let list = harness.node_id("list");
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state.set_scroll_offset(list, egui::vec2(0., 310.));
+    });
+    harness.run(sections(&shown));
+    assert_eq!(*shown.borrow(), [5, 6]);
+}
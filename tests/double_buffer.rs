@@ -0,0 +1,41 @@
+mod common;
+
+use egui_taffy::convergence::{ConvergencePolicy, ConvergenceStrategy};
+use egui_taffy::{TuiBuilderLogic, tui};
+
+/// Run single frame with the pointer at given position, returns button response
+fn frame(ctx: &egui::Context, pointer: egui::Pos2, labels: usize) -> egui::Response {
+    let mut response = None;
+    common::frame(ctx, vec![egui::Event::PointerMoved(pointer)], |ui| {
+        tui(ui, "double_buffered")
+            .reserve_available_space()
+            .double_buffer(true)
+            .convergence(ConvergencePolicy::default().strategy(ConvergenceStrategy::NextFrame))
+            .show(|tui| {
+                response = Some(tui.id("button").ui(|ui| ui.button("Button")));
+                for label in 0..labels {
+                    tui.id(egui::Id::new(label)).label("Label");
+                }
+            });
+    });
+    response.unwrap()
+}
+
+#[test]
+fn interaction_is_blocked_while_stale_paint_is_shown() {
+    let ctx = egui::Context::default();
+
+    let mut button = frame(&ctx, egui::Pos2::ZERO, 0);
+    for _ in 0..3 {
+        button = frame(&ctx, button.rect.center(), 0);
+    }
+    assert!(button.hovered());
+
+    // New label is measured while the previous settled layout is painted
+    let hovered: Vec<bool> = (0..6)
+        .map(|_| frame(&ctx, button.rect.center(), 1).hovered())
+        .collect();
+    // Hover is based on widgets of the previous frame. Label is added in the first frame
+    // and its measured size is applied in the second frame, then the layout settles.
+    assert_eq!(hovered, [true, false, false, true, true, true]);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use egui_taffy::taffy::prelude::*;
+use egui_taffy::{TaffyState, TuiBuilderLogic, TuiId, testing::TuiTestHarness};
+
+use common::fixed;
+
+fn node_count(harness: &TuiTestHarness) -> usize {
+    TaffyState::with(harness.ctx(), harness.id(), |state| state.items().len()).unwrap()
+}
+
+fn unique_node(harness: &TuiTestHarness) -> Option<NodeId> {
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state
+            .items()
+            .get(&egui::Id::new("moved"))
+            .map(|data| data.node_id)
+    })
+    .flatten()
+}
+
+#[test]
+fn removed_subtree_is_collected() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let layout = |with_group: bool| {
+        move |tui: &mut egui_taffy::Tui| {
+            tui.id("kept").style(fixed(20., 20.)).add_empty();
+            if with_group {
+                tui.id("group").add(|tui| {
+                    tui.id("child").add(|tui| {
+                        tui.id("grandchild").style(fixed(20., 20.)).add_empty();
+                    });
+                    tui.id("sibling").style(fixed(20., 20.)).add_empty();
+                });
+            }
+        }
+    };
+
+    harness.run(layout(false));
+    let without_group = node_count(&harness);
+
+    let result = harness.run(layout(true));
+    assert!(result.rect("grandchild").is_some());
+    assert_eq!(node_count(&harness), without_group + 4);
+
+    let result = harness.run(layout(false));
+    assert!(result.rect("grandchild").is_none());
+    assert_eq!(node_count(&harness), without_group);
+}
+
+#[test]
+fn moved_node_keeps_its_node() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let layout = |parent: &'static str| {
+        move |tui: &mut egui_taffy::Tui| {
+            for name in ["first", "second"] {
+                tui.id(name).add(|tui| {
+                    if name == parent {
+                        tui.id(TuiId::Unique(egui::Id::new("moved")))
+                            .style(fixed(20., 20.))
+                            .add_empty();
+                    }
+                });
+            }
+        }
+    };
+
+    harness.run(layout("first"));
+    let node = unique_node(&harness).unwrap();
+    let count = node_count(&harness);
+
+    let result = harness.run(layout("second"));
+    assert_eq!(unique_node(&harness), Some(node));
+    assert_eq!(node_count(&harness), count);
+    assert!(
+        result
+            .rect("second")
+            .unwrap()
+            .contains_rect(result.rect_by_egui_id(egui::Id::new("moved")).unwrap())
+    );
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use egui_taffy::animation::LayoutAnimation;
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+
+use common::fixed;
+
+#[test]
+fn harness_rect_includes_sticky_offset() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let mut scroll_requested = false;
+    let mut layout = |tui: &mut egui_taffy::Tui| {
+        if !scroll_requested {
+            scroll_requested = true;
+            tui.set_scroll_offset("list", egui::vec2(0., 200.));
+        }
+        tui.id("list")
+            .style(taffy::Style {
+                flex_direction: taffy::FlexDirection::Column,
+                overflow: taffy::Point {
+                    x: taffy::Overflow::Visible,
+                    y: taffy::Overflow::Scroll,
+                },
+                size: taffy::Size {
+                    width: length(200.),
+                    height: length(100.),
+                },
+                ..Default::default()
+            })
+            .add(|tui| {
+                for row in 0..5 {
+                    tui.id(egui::Id::new(row))
+                        .style(fixed(200., 30.))
+                        .add_empty();
+                }
+                tui.id("header")
+                    .style(taffy::Style {
+                        inset: taffy::Rect {
+                            top: length(0.),
+                            ..taffy::Rect::auto()
+                        },
+                        ..fixed(200., 20.)
+                    })
+                    .position_sticky()
+                    .add_empty();
+                for row in 5..20 {
+                    tui.id(egui::Id::new(row))
+                        .style(fixed(200., 30.))
+                        .add_empty();
+                }
+            });
+    };
+
+    harness.run(&mut layout);
+    let result = harness.run(&mut layout);
+
+    let list = result.rect("list").unwrap();
+    let header = result.rect("header").unwrap();
+    // Header is stuck at the top of the viewport instead of its scrolled position
+    assert_eq!(header.top(), list.top());
+    assert_eq!(
+        result.rect(egui::Id::new(4)).unwrap().bottom(),
+        list.top() + 150. - 200.
+    );
+}
+
+#[test]
+fn harness_rect_includes_layout_animation() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let layout = |offset: f32| {
+        move |tui: &mut egui_taffy::Tui| {
+            tui.id("spacer").style(fixed(offset, 20.)).add_empty();
+            tui.id("box")
+                .style(fixed(50., 20.))
+                .animate_layout(LayoutAnimation::new(1000.))
+                .add_empty();
+        }
+    };
+
+    let start = harness.run(layout(0.)).rect("box").unwrap();
+    let result = harness.run_frames(3, layout(100.));
+
+    // Box is painted close to its previous position at the start of the animation
+    let painted = result.rect("box").unwrap();
+    assert!((painted.left() - start.left()).abs() < 1., "{painted:?}");
+    let root = result.snapshot().root.as_ref().unwrap();
+    assert_eq!(root.children[1].location.x, 100.);
+}
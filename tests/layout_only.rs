@@ -0,0 +1,47 @@
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+
+fn wrapper() -> taffy::Style {
+    taffy::Style {
+        padding: length(10.),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn layout_only_node_places_children_in_parent_ui() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let mut ui_ids = None;
+    let result = harness.run(|tui| {
+        let parent = tui.egui_ui().id();
+        let layout_only = tui
+            .id("layout_only")
+            .style(wrapper())
+            .layout_only()
+            .add(|tui| {
+                tui.id("first").label("Label");
+                tui.egui_ui().id()
+            });
+        let regular = tui.id("regular").style(wrapper()).add(|tui| {
+            tui.id("second").label("Label");
+            tui.egui_ui().id()
+        });
+        ui_ids = Some((parent, layout_only.unwrap(), regular.unwrap()));
+    });
+
+    let (parent, layout_only, regular) = ui_ids.unwrap();
+    assert_eq!(layout_only, parent);
+    assert_ne!(regular, parent);
+
+    // Layout is the same as with child ui
+    let (first, second) = (
+        result.rect("first").unwrap(),
+        result.rect("second").unwrap(),
+    );
+    assert_eq!(first.size(), second.size());
+    assert_eq!(
+        first.min - result.rect("layout_only").unwrap().min,
+        second.min - result.rect("regular").unwrap().min
+    );
+}
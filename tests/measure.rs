@@ -0,0 +1,103 @@
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TuiBuilderLogic, TuiMeasure, testing::TuiTestHarness};
+
+/// Measures text using egui fonts during layout calculation
+struct TextMeasure {
+    ctx: egui::Context,
+    text: String,
+}
+
+impl PartialEq for TextMeasure {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+    }
+}
+
+impl TuiMeasure for TextMeasure {
+    fn measure(
+        &self,
+        _known_dimensions: taffy::Size<Option<f32>>,
+        _available_space: taffy::Size<taffy::AvailableSpace>,
+    ) -> taffy::Size<f32> {
+        let size = self.ctx.fonts(|fonts| {
+            fonts
+                .layout_no_wrap(
+                    self.text.clone(),
+                    egui::FontId::default(),
+                    egui::Color32::WHITE,
+                )
+                .size()
+        });
+        taffy::Size {
+            width: size.x,
+            height: size.y,
+        }
+    }
+}
+
+/// Keeps width to height ratio
+#[derive(PartialEq)]
+struct AspectRatio(f32);
+
+impl TuiMeasure for AspectRatio {
+    fn measure(
+        &self,
+        known_dimensions: taffy::Size<Option<f32>>,
+        available_space: taffy::Size<taffy::AvailableSpace>,
+    ) -> taffy::Size<f32> {
+        let width = known_dimensions
+            .width
+            .unwrap_or(available_space.width.unwrap_or(0.));
+        taffy::Size {
+            width,
+            height: width / self.0,
+        }
+    }
+}
+
+#[test]
+fn custom_measure_can_lay_out_text() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let text = "Measured text";
+
+    let ctx = harness.ctx().clone();
+    let result = harness.run(|tui| {
+        let measure = TextMeasure {
+            ctx: ctx.clone(),
+            text: text.into(),
+        };
+        tui.id("text").ui_measure(measure, |_ui| {});
+    });
+
+    // Fonts are available after the first frame
+    let expected = harness.ctx().fonts(|fonts| {
+        fonts
+            .layout_no_wrap(text.into(), egui::FontId::default(), egui::Color32::WHITE)
+            .size()
+    });
+    // Layout is rounded to whole pixels
+    let size = result.rect("text").unwrap().size();
+    assert!(
+        (size - expected).abs().max_elem() <= 0.5,
+        "{size:?} != {expected:?}"
+    );
+}
+
+#[test]
+fn custom_measure_uses_known_width() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let result = harness.run(|tui| {
+        tui.id("image")
+            .style(taffy::Style {
+                size: taffy::Size {
+                    width: length(300.),
+                    height: auto(),
+                },
+                ..Default::default()
+            })
+            .ui_measure(AspectRatio(2.), |_ui| {});
+    });
+
+    assert_eq!(result.rect("image").unwrap().size(), egui::vec2(300., 150.));
+}
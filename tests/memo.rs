@@ -0,0 +1,71 @@
+mod common;
+
+use std::cell::Cell;
+
+use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+
+use common::fixed;
+
+/// Memoized node after a spacer, painted rectangle of the memoized content is stored in `painted`
+fn layout<'a>(
+    offset: f32,
+    runs: &'a Cell<usize>,
+    painted: &'a Cell<Option<egui::Rect>>,
+) -> impl FnMut(&mut egui_taffy::Tui) + 'a {
+    move |tui| {
+        tui.id("spacer").style(fixed(offset, 20.)).add_empty();
+        tui.id("memo").style(fixed(50., 20.)).add_memo(0, |tui| {
+            runs.set(runs.get() + 1);
+            let rect = tui.egui_ui().max_rect();
+            tui.egui_ui()
+                .painter()
+                .rect_filled(rect, 0., egui::Color32::RED);
+        });
+
+        let ui = tui.egui_ui();
+        painted.set(ui.ctx().graphics(|graphics| {
+            graphics.get(ui.layer_id()).and_then(|paint_list| {
+                paint_list
+                    .all_entries()
+                    .map(|shape| shape.shape.visual_bounding_rect())
+                    .find(|rect| rect.width() == 50.)
+            })
+        }));
+    }
+}
+
+#[test]
+fn moved_memo_node_is_replayed() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let runs = Cell::new(0);
+    let painted = Cell::new(None);
+
+    harness.run_frames(3, layout(0., &runs, &painted));
+    let runs_before = runs.get();
+
+    let result = harness.run_frames(3, layout(100., &runs, &painted));
+    assert_eq!(runs.get(), runs_before);
+    // Recorded shapes are painted at the new node position
+    let memo = result.rect("memo").unwrap();
+    assert_eq!(memo.left(), 100.);
+    assert_eq!(painted.get(), Some(memo));
+}
+
+#[test]
+fn memo_content_is_shown_again_when_style_changes() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let runs = Cell::new(0);
+    let painted = Cell::new(None);
+
+    harness.run_frames(3, layout(0., &runs, &painted));
+    let runs_before = runs.get();
+
+    harness.ctx().style_mut(|style| {
+        style.visuals.override_text_color = Some(egui::Color32::GREEN);
+    });
+    harness.run_frames(1, layout(0., &runs, &painted));
+    assert_eq!(runs.get(), runs_before + 1);
+
+    harness.run_frames(2, layout(0., &runs, &painted));
+    assert_eq!(runs.get(), runs_before + 1);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use std::sync::Arc;
+
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TaffyState, Tui, TuiBuilderLogic, tui};
+
+const TUI_ID: &str = "persisted";
+
+struct App {
+    ctx: egui::Context,
+}
+
+impl App {
+    fn new() -> Self {
+        let ctx = egui::Context::default();
+        ctx.options_mut(|options| {
+            options.max_passes = std::num::NonZeroUsize::new(3).unwrap();
+        });
+        Self { ctx }
+    }
+
+    /// Run single frame, returns if any pass of the frame was discarded
+    fn frame(&self, persist_layout: bool, mut f: impl FnMut(&mut Tui)) -> bool {
+        let output = common::frame(&self.ctx, Vec::new(), |ui| {
+            tui(ui, TUI_ID)
+                .reserve_available_space()
+                .persist_layout(persist_layout)
+                .show(&mut f);
+        });
+        output.platform_output.requested_discard()
+    }
+
+    fn recalculations(&self) -> usize {
+        TaffyState::with(&self.ctx, egui::Id::new(TUI_ID), |state| {
+            state.convergence_report().recalculations
+        })
+        .unwrap()
+    }
+
+    /// Drop layout state as if application was restarted, persisted memory is kept
+    fn drop_state(&self) {
+        self.ctx.data_mut(|data| {
+            data.remove_by_type::<Arc<parking_lot::Mutex<TaffyState>>>();
+        });
+    }
+}
+
+fn layout(tui: &mut Tui) {
+    tui.id("row")
+        .style(taffy::Style {
+            gap: length(8.),
+            ..Default::default()
+        })
+        .add(|tui| {
+            tui.label("First label");
+            tui.label("Second label");
+        });
+}
+
+fn settle(app: &App, persist_layout: bool) {
+    for _ in 0..5 {
+        app.frame(persist_layout, layout);
+    }
+}
+
+#[test]
+fn stored_layout_is_restored() {
+    let app = App::new();
+    settle(&app, true);
+
+    app.drop_state();
+    let discarded = app.frame(true, layout);
+    assert!(!discarded);
+    assert_eq!(app.recalculations(), 0);
+}
+
+#[test]
+fn layout_is_not_restored_when_persistence_is_disabled() {
+    let app = App::new();
+    settle(&app, true);
+
+    app.drop_state();
+    app.frame(false, layout);
+    assert!(app.recalculations() > 0);
+}
+
+#[test]
+fn layout_is_not_restored_after_reset() {
+    let app = App::new();
+    settle(&app, true);
+
+    app.frame(true, |tui| {
+        layout(tui);
+        tui.reset_state();
+    });
+    app.frame(true, layout);
+    assert!(app.recalculations() > 0);
+}
@@ -0,0 +1,73 @@
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TaffyState, TuiBuilderLogic, testing::TuiTestHarness};
+
+fn row_style() -> taffy::Style {
+    taffy::Style {
+        size: taffy::Size {
+            width: length(100.),
+            height: length(20.),
+        },
+        flex_shrink: 0.,
+        ..Default::default()
+    }
+}
+
+fn show_rows(harness: &mut TuiTestHarness, rows: &[&str]) -> egui_taffy::testing::TuiTestResult {
+    harness.run(|tui| {
+        tui.id("list")
+            .style(taffy::Style {
+                flex_direction: taffy::FlexDirection::Column,
+                ..Default::default()
+            })
+            .add(|tui| {
+                for row in rows {
+                    tui.id(*row).style(row_style()).add_empty();
+                }
+            });
+    })
+}
+
+/// Taffy node of the list row
+fn row_node(harness: &TuiTestHarness, row: &str) -> Option<NodeId> {
+    let list = harness.node_id("list");
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state
+            .items()
+            .get(&list.with(egui::Id::new(row)))
+            .map(|data| data.node_id)
+    })
+    .flatten()
+}
+
+#[test]
+fn reordered_children_keep_their_nodes() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    show_rows(&mut harness, &["a", "b", "c"]);
+    let nodes = ["a", "b", "c"].map(|row| row_node(&harness, row).unwrap());
+
+    let result = show_rows(&mut harness, &["c", "a", "b"]);
+    assert_eq!(
+        ["a", "b", "c"].map(|row| row_node(&harness, row).unwrap()),
+        nodes
+    );
+
+    let top = |row: &str| result.rect(row).unwrap().top();
+    assert!(top("c") < top("a"));
+    assert!(top("a") < top("b"));
+}
+
+#[test]
+fn removed_children_are_collected() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    show_rows(&mut harness, &["a", "b", "c"]);
+    let result = show_rows(&mut harness, &["a", "new", "c"]);
+
+    assert!(row_node(&harness, "b").is_none());
+    assert!(result.rect("b").is_none());
+    assert_eq!(
+        result.rect("new").unwrap().top(),
+        result.rect("a").unwrap().bottom()
+    );
+}
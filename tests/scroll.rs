@@ -0,0 +1,369 @@
+use egui_taffy::scroll::ScrollConfig;
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TaffyState, TuiBuilderLogic, testing::TuiTestHarness};
+
+/// Add scrollable list of rows
+fn list(tui: &mut egui_taffy::Tui, id: &str, rows: usize, config: ScrollConfig) {
+    tui.id(id)
+        .style(taffy::Style {
+            flex_direction: taffy::FlexDirection::Column,
+            overflow: taffy::Point {
+                x: taffy::Overflow::Visible,
+                y: taffy::Overflow::Scroll,
+            },
+            size: taffy::Size {
+                width: length(200.),
+                height: length(100.),
+            },
+            ..Default::default()
+        })
+        .scroll_config(config)
+        .add(|tui| {
+            for row in 0..rows {
+                tui.id(egui::Id::new(row))
+                    .style(taffy::Style {
+                        size: taffy::Size {
+                            width: length(200.),
+                            height: length(30.),
+                        },
+                        flex_shrink: 0.,
+                        ..Default::default()
+                    })
+                    .add_empty();
+            }
+        });
+}
+
+/// Scrollable log with given amount of rows that sticks to the bottom
+fn log(rows: usize) -> impl FnMut(&mut egui_taffy::Tui) {
+    move |tui| {
+        list(
+            tui,
+            "log",
+            rows,
+            ScrollConfig::default().stick_to_bottom(true),
+        )
+    }
+}
+
+#[test]
+fn stick_to_bottom_follows_new_content() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let result = harness.run(log(10));
+    let bottom = result.rect("log").unwrap().bottom();
+    assert_eq!(result.rect(egui::Id::new(9)).unwrap().bottom(), bottom);
+
+    let result = harness.run(log(12));
+    assert_eq!(result.rect(egui::Id::new(11)).unwrap().bottom(), bottom);
+}
+
+#[test]
+fn stick_to_bottom_stops_after_user_scrolls_away() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    harness.run(log(10));
+    harness.scroll("log", egui::vec2(0., 60.));
+    // Mouse wheel scrolling is smoothed over several frames
+    let scrolled = harness.run_frames(60, log(10));
+    let last_row = scrolled.rect(egui::Id::new(9)).unwrap();
+    assert!(last_row.bottom() > scrolled.rect("log").unwrap().bottom());
+
+    // Viewport stays at the same content position
+    let result = harness.run(log(12));
+    assert_eq!(result.rect(egui::Id::new(9)).unwrap(), last_row);
+}
+
+#[test]
+fn scroll_to_moves_node_into_view() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let layout = |tui: &mut egui_taffy::Tui| {
+        list(tui, "list", 20, ScrollConfig::default().animated(false));
+    };
+
+    harness.run(layout);
+    let row = harness.node_id(egui::Id::new(15));
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state.scroll_to(row, Some(egui::Align::TOP));
+    });
+
+    let result = harness.run_frames(3, layout);
+    // egui keeps item spacing between the scrolled to rectangle and the viewport edge
+    let spacing = harness.ctx().style().spacing.item_spacing.y;
+    assert_eq!(
+        result.rect(egui::Id::new(15)).unwrap().top(),
+        result.rect("list").unwrap().top() + spacing
+    );
+}
+
+#[test]
+fn scroll_offset_is_set_and_reported() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let layout = |tui: &mut egui_taffy::Tui| {
+        list(tui, "list", 20, ScrollConfig::default());
+    };
+
+    harness.run(layout);
+    let list = harness.node_id("list");
+    let scroll_info = |harness: &TuiTestHarness| {
+        TaffyState::with(harness.ctx(), harness.id(), |state| state.scroll_info(list))
+            .flatten()
+            .unwrap()
+    };
+    let info = scroll_info(&harness);
+    assert_eq!(info.offset, egui::Vec2::ZERO);
+    assert_eq!(info.content_size, egui::vec2(200., 600.));
+    assert_eq!(info.viewport_size, egui::vec2(200., 100.));
+
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state.set_scroll_offset(list, egui::vec2(0., 90.));
+    });
+    let result = harness.run(layout);
+    assert_eq!(scroll_info(&harness).offset, egui::vec2(0., 90.));
+    assert_eq!(
+        result.rect(egui::Id::new(3)).unwrap().top(),
+        result.rect("list").unwrap().top()
+    );
+
+    // Offset is clamped to the content size
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state.set_scroll_offset(list, egui::vec2(0., 1000.));
+    });
+    harness.run(layout);
+    let info = scroll_info(&harness);
+    assert_eq!(info.offset, egui::vec2(0., 500.));
+    assert!(info.stuck_to_end.y);
+}
+
+#[test]
+fn overridden_overflow_makes_node_scrollable() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let list_style = taffy::Style {
+        flex_direction: taffy::FlexDirection::Column,
+        size: taffy::Size {
+            width: length(200.),
+            height: length(100.),
+        },
+        ..Default::default()
+    };
+    let layout = |tui: &mut egui_taffy::Tui| {
+        tui.id("list").style(list_style.clone()).add(|tui| {
+            for row in 0..20 {
+                tui.id(egui::Id::new(row))
+                    .style(taffy::Style {
+                        size: taffy::Size {
+                            width: length(200.),
+                            height: length(30.),
+                        },
+                        flex_shrink: 0.,
+                        ..Default::default()
+                    })
+                    .add(|tui| {
+                        let rect = tui.egui_ui().max_rect();
+                        tui.egui_ui()
+                            .painter()
+                            .rect_filled(rect, 0., egui::Color32::RED);
+                    });
+            }
+        });
+    };
+
+    harness.run(layout);
+    let list = harness.node_id("list");
+    let scroll_info = |harness: &TuiTestHarness| {
+        TaffyState::with(harness.ctx(), harness.id(), |state| state.scroll_info(list)).flatten()
+    };
+    assert!(scroll_info(&harness).is_none());
+
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        let mut style = list_style.clone();
+        style.overflow.y = taffy::Overflow::Scroll;
+        state.set_style_override(list, style);
+        state.set_scroll_offset(list, egui::vec2(0., 90.));
+    });
+    harness.run(layout);
+    let mut clip_rects = Vec::new();
+    let result = harness.run_frames(1, |tui| {
+        layout(tui);
+        let ui = tui.egui_ui();
+        clip_rects = ui.ctx().graphics(|graphics| {
+            graphics
+                .get(ui.layer_id())
+                .map(|paint_list| {
+                    paint_list
+                        .all_entries()
+                        .filter(|shape| shape.shape.visual_bounding_rect().width() == 200.)
+                        .map(|shape| shape.clip_rect)
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+    });
+    let info = scroll_info(&harness).unwrap();
+    assert_eq!(info.offset, egui::vec2(0., 90.));
+    assert_eq!(info.content_size, egui::vec2(200., 600.));
+
+    // Rows are clipped by the scroll viewport
+    let list_rect = result.rect("list").unwrap();
+    assert_eq!(
+        result.rect(egui::Id::new(3)).unwrap().top(),
+        list_rect.top()
+    );
+    assert!(!clip_rects.is_empty());
+    assert!(
+        clip_rects
+            .iter()
+            .all(|clip_rect| clip_rect.y_range() == list_rect.y_range()),
+        "{clip_rects:?}"
+    );
+}
+
+fn list_scroll_info(harness: &TuiTestHarness, id: &str) -> egui_taffy::scroll::ScrollInfo {
+    let list = harness.node_id(id);
+    TaffyState::with(harness.ctx(), harness.id(), |state| state.scroll_info(list))
+        .flatten()
+        .unwrap()
+}
+
+#[test]
+fn scroll_bar_visibility_is_applied() {
+    use egui::scroll_area::ScrollBarVisibility;
+
+    let painted_shapes = |visibility| {
+        let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+        let mut shapes = 0;
+        harness.run(|tui| {
+            list(
+                tui,
+                "list",
+                20,
+                ScrollConfig::default().scroll_bar_visibility(visibility),
+            );
+            let ui = tui.egui_ui();
+            shapes = ui.ctx().graphics(|graphics| {
+                graphics.get(ui.layer_id()).map_or(0, |paint_list| {
+                    paint_list
+                        .all_entries()
+                        .filter(|shape| !matches!(shape.shape, egui::Shape::Noop))
+                        .count()
+                })
+            });
+        });
+        shapes
+    };
+
+    // Rows do not paint anything, only scroll bar is painted
+    assert_eq!(painted_shapes(ScrollBarVisibility::AlwaysHidden), 0);
+    assert!(painted_shapes(ScrollBarVisibility::AlwaysVisible) > 0);
+}
+
+#[test]
+fn scroll_source_is_applied() {
+    use egui::scroll_area::ScrollSource;
+
+    let wheel_scrolled = |config: ScrollConfig| {
+        let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+        let layout = |tui: &mut egui_taffy::Tui| list(tui, "list", 20, config);
+        harness.run(layout);
+        harness.scroll("list", egui::vec2(0., -50.));
+        harness.run(layout);
+        list_scroll_info(&harness, "list").offset.y > 0.
+    };
+
+    assert!(wheel_scrolled(ScrollConfig::default()));
+    assert!(!wheel_scrolled(
+        ScrollConfig::default().scroll_source(ScrollSource::SCROLL_BAR)
+    ));
+}
+
+#[test]
+fn auto_shrink_is_applied() {
+    let wheel_scrolled = |config: ScrollConfig| {
+        let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+        let layout = |tui: &mut egui_taffy::Tui| {
+            tui.id("list")
+                .style(taffy::Style {
+                    flex_direction: taffy::FlexDirection::Column,
+                    align_items: Some(taffy::AlignItems::Start),
+                    overflow: taffy::Point {
+                        x: taffy::Overflow::Visible,
+                        y: taffy::Overflow::Scroll,
+                    },
+                    size: taffy::Size {
+                        width: length(200.),
+                        height: length(100.),
+                    },
+                    ..Default::default()
+                })
+                .scroll_config(config)
+                .add(|tui| {
+                    for row in 0..20 {
+                        tui.id(egui::Id::new(row))
+                            .style(taffy::Style {
+                                size: taffy::Size {
+                                    width: length(50.),
+                                    height: length(30.),
+                                },
+                                flex_shrink: 0.,
+                                ..Default::default()
+                            })
+                            .add_empty();
+                    }
+                });
+        };
+        harness.run(layout);
+        // Pointer is outside of the narrow content
+        harness.scroll("list", egui::vec2(0., -50.));
+        harness.run(layout);
+        list_scroll_info(&harness, "list").offset.y > 0.
+    };
+
+    // Shrunk scroll area covers only the content width
+    assert!(!wheel_scrolled(ScrollConfig::default().auto_shrink(true)));
+    assert!(wheel_scrolled(ScrollConfig::default().auto_shrink(false)));
+}
+
+#[test]
+fn animated_is_applied() {
+    let scroll_to_row = |config: ScrollConfig| {
+        let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+        let layout = |tui: &mut egui_taffy::Tui| list(tui, "list", 20, config);
+        harness.run(layout);
+        let row = harness.node_id(egui::Id::new(15));
+        TaffyState::with(harness.ctx(), harness.id(), |state| {
+            state.scroll_to(row, Some(egui::Align::TOP));
+        });
+        let first_frames = harness.run_frames(2, layout);
+        let finished = harness.run(layout);
+        (
+            first_frames.rect(egui::Id::new(15)).unwrap(),
+            finished.rect(egui::Id::new(15)).unwrap(),
+        )
+    };
+
+    let (first_frames, finished) = scroll_to_row(ScrollConfig::default().animated(false));
+    assert_eq!(first_frames, finished);
+    let (first_frames, finished) = scroll_to_row(ScrollConfig::default().animated(true));
+    assert_ne!(first_frames, finished);
+}
+
+#[test]
+fn id_salt_keeps_scroll_state_when_node_id_changes() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let config = ScrollConfig::default().id_salt("messages");
+
+    harness.run(|tui| list(tui, "a", 20, config));
+    let a = harness.node_id("a");
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state.set_scroll_offset(a, egui::vec2(0., 90.));
+    });
+    harness.run(|tui| list(tui, "a", 20, config));
+    assert_eq!(list_scroll_info(&harness, "a").offset, egui::vec2(0., 90.));
+
+    harness.run(|tui| list(tui, "b", 20, config));
+    assert_eq!(list_scroll_info(&harness, "b").offset, egui::vec2(0., 90.));
+
+    // Without salt scroll state belongs to the node id
+    harness.run(|tui| list(tui, "c", 20, ScrollConfig::default()));
+    assert_eq!(list_scroll_info(&harness, "c").offset, egui::Vec2::ZERO);
+}
@@ -0,0 +1,91 @@
+use egui_taffy::convergence::NodeChangeKind;
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{SharedStyle, TaffyState, TuiBuilderLogic, testing::TuiTestHarness};
+
+fn row_style(height: f32) -> SharedStyle {
+    SharedStyle::new(taffy::Style {
+        size: taffy::Size {
+            width: length(100.),
+            height: length(height),
+        },
+        ..Default::default()
+    })
+}
+
+/// Count of node style changes recorded in the last frame
+fn style_changes(harness: &TuiTestHarness) -> usize {
+    TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state
+            .convergence_report()
+            .changes
+            .iter()
+            .filter(|change| change.kind == NodeChangeKind::Style)
+            .count()
+    })
+    .unwrap()
+}
+
+fn show_rows(
+    harness: &mut TuiTestHarness,
+    style: &SharedStyle,
+) -> egui_taffy::testing::TuiTestResult {
+    harness.run(|tui| {
+        for row in 0..3 {
+            tui.id(egui::Id::new(row)).shared_style(style).add_empty();
+        }
+    })
+}
+
+#[test]
+fn shared_style_changes_are_detected() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let style = row_style(20.);
+    show_rows(&mut harness, &style);
+    show_rows(&mut harness, &style);
+    assert_eq!(style_changes(&harness), 0);
+
+    // Equal style in a new shared instance is compared by value
+    show_rows(&mut harness, &row_style(20.));
+    assert_eq!(style_changes(&harness), 0);
+
+    let result = show_rows(&mut harness, &row_style(30.));
+    assert_eq!(
+        result.rect(egui::Id::new(0)).unwrap().size(),
+        egui::vec2(100., 30.)
+    );
+}
+
+#[test]
+fn equal_styles_are_stored_once() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let style_count = |harness: &TuiTestHarness| {
+        TaffyState::with(harness.ctx(), harness.id(), |state| state.style_count()).unwrap()
+    };
+    let rows = |height: f32| {
+        let shared = row_style(height);
+        move |tui: &mut egui_taffy::Tui| {
+            for row in 0..3 {
+                tui.id(egui::Id::new(row))
+                    .style(shared.style().clone())
+                    .add_empty();
+            }
+            for row in 3..6 {
+                tui.id(egui::Id::new(row)).shared_style(&shared).add_empty();
+            }
+        }
+    };
+
+    harness.run(|_| {});
+    let root_styles = style_count(&harness);
+
+    // Owned styles and shared style with equal values
+    harness.run(rows(20.));
+    assert_eq!(style_count(&harness), root_styles + 1);
+
+    // Styles that are not used anymore are released
+    harness.run(rows(30.));
+    assert_eq!(style_count(&harness), root_styles + 1);
+    harness.run(|_| {});
+    assert_eq!(style_count(&harness), root_styles);
+}
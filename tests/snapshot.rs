@@ -0,0 +1,60 @@
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+
+fn layout(tui: &mut egui_taffy::Tui) {
+    let fixed = taffy::Style {
+        size: length(20.),
+        ..Default::default()
+    };
+    tui.id("first").style(fixed.clone()).add_empty();
+    tui.id("second")
+        .style(taffy::Style {
+            padding: length(2.),
+            ..Default::default()
+        })
+        .add(|tui| {
+            tui.id("nested").style(fixed.clone()).add_empty();
+        });
+}
+
+#[test]
+fn snapshot_nodes_are_identified_by_path() {
+    let mut harness = TuiTestHarness::new(egui::vec2(200., 100.));
+    let result = harness.run(layout);
+
+    let root = result.snapshot().root.as_ref().unwrap();
+    assert_eq!(root.path, "/");
+    let paths: Vec<&str> = root
+        .children
+        .iter()
+        .map(|node| node.path.as_str())
+        .collect();
+    assert_eq!(paths, ["/0", "/1"]);
+    assert_eq!(root.children[1].children[0].path, "/1/0");
+
+    let id = |node: &egui_taffy::snapshot::NodeSnapshot| format!("{:016x}", node.id.value());
+    assert_eq!(root.children[0].id, harness.node_id("first"));
+    assert_eq!(
+        result.snapshot().to_pretty_text(),
+        format!(
+            "/ id={} location=(0, 0) size=(44 x 24) content_size=(44 x 24)\n\
+            \x20 /0 id={} location=(0, 0) size=(20 x 20) content_size=(0 x 0)\n\
+            \x20 /1 id={} location=(20, 0) size=(24 x 24) content_size=(24 x 24) \
+            padding=[2 2 2 2]\n\
+            \x20   /1/0 id={} location=(2, 2) size=(20 x 20) content_size=(0 x 0)\n",
+            id(root),
+            id(&root.children[0]),
+            id(&root.children[1]),
+            id(&root.children[1].children[0]),
+        )
+    );
+}
+
+#[test]
+fn snapshot_is_stable_between_instances() {
+    let snapshot = || {
+        let mut harness = TuiTestHarness::new(egui::vec2(200., 100.));
+        harness.run(layout).snapshot().to_json()
+    };
+    assert_eq!(snapshot(), snapshot());
+}
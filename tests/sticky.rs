@@ -0,0 +1,286 @@
+mod common;
+
+use std::cell::Cell;
+
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+
+use common::fixed;
+
+fn scroll_list(height: f32) -> taffy::Style {
+    taffy::Style {
+        flex_direction: taffy::FlexDirection::Column,
+        overflow: taffy::Point {
+            x: taffy::Overflow::Visible,
+            y: taffy::Overflow::Scroll,
+        },
+        flex_shrink: 0.,
+        size: taffy::Size {
+            width: length(200.),
+            height: length(height),
+        },
+        ..Default::default()
+    }
+}
+
+fn sticky_header(inset: taffy::Rect<LengthPercentageAuto>) -> taffy::Style {
+    taffy::Style {
+        inset,
+        ..fixed(200., 20.)
+    }
+}
+
+#[test]
+fn stuck_header_is_above_following_content() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let header_clicks = Cell::new(0);
+    let row_clicks = Cell::new(0);
+    let mut scroll_requested = false;
+    let mut layout = |tui: &mut egui_taffy::Tui| {
+        if !scroll_requested {
+            scroll_requested = true;
+            tui.set_scroll_offset("list", egui::vec2(0., 200.));
+        }
+        tui.id("list").style(scroll_list(100.)).add(|tui| {
+            let row = |tui: &mut egui_taffy::Tui, row: usize| {
+                let response = tui
+                    .id(egui::Id::new(row))
+                    .style(fixed(200., 30.))
+                    .button(|tui| tui.label("Row"));
+                if response.clicked() {
+                    row_clicks.set(row_clicks.get() + 1);
+                }
+            };
+            for idx in 0..5 {
+                row(tui, idx);
+            }
+            let response = tui
+                .id("header")
+                .style(sticky_header(taffy::Rect {
+                    top: length(0.),
+                    ..taffy::Rect::auto()
+                }))
+                .position_sticky()
+                .button(|tui| tui.label("Header"));
+            if response.clicked() {
+                header_clicks.set(header_clicks.get() + 1);
+            }
+            for idx in 5..20 {
+                row(tui, idx);
+            }
+        });
+    };
+
+    harness.run(&mut layout);
+    let result = harness.run(&mut layout);
+    // Following row is scrolled under the stuck header
+    let header = result.rect("header").unwrap();
+    assert!(result.rect(egui::Id::new(6)).unwrap().intersects(header));
+
+    harness.click("header");
+    harness.run(&mut layout);
+    assert_eq!(header_clicks.get(), 1);
+    assert_eq!(row_clicks.get(), 0);
+}
+
+#[test]
+fn sticky_header_sticks_to_scroll_viewport() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let layout = |tui: &mut egui_taffy::Tui| {
+        // Clipping ancestor is smaller than the scrollable list
+        tui.id("clip")
+            .style(taffy::Style {
+                overflow: taffy::Point {
+                    x: taffy::Overflow::Hidden,
+                    y: taffy::Overflow::Hidden,
+                },
+                ..fixed(200., 50.)
+            })
+            .add(|tui| {
+                tui.id("list").style(scroll_list(100.)).add(|tui| {
+                    for row in 0..10 {
+                        tui.id(egui::Id::new(row))
+                            .style(fixed(200., 30.))
+                            .add_empty();
+                    }
+                    tui.id("header")
+                        .style(sticky_header(taffy::Rect {
+                            bottom: length(0.),
+                            ..taffy::Rect::auto()
+                        }))
+                        .position_sticky()
+                        .add_empty();
+                });
+            });
+    };
+
+    harness.run(layout);
+    let result = harness.run(layout);
+
+    let list = result.rect("list").unwrap();
+    assert_eq!(result.rect("header").unwrap().bottom(), list.bottom());
+}
+
+#[test]
+fn overridden_sticky_inset_is_used_as_threshold() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let mut scroll_requested = false;
+    let mut layout = |tui: &mut egui_taffy::Tui| {
+        if !scroll_requested {
+            scroll_requested = true;
+            tui.set_scroll_offset("list", egui::vec2(0., 200.));
+        }
+        tui.id("list").style(scroll_list(100.)).add(|tui| {
+            tui.id("header")
+                .style(sticky_header(taffy::Rect {
+                    top: length(0.),
+                    ..taffy::Rect::auto()
+                }))
+                .position_sticky()
+                .add_empty();
+            for row in 0..20 {
+                tui.id(egui::Id::new(row))
+                    .style(fixed(200., 30.))
+                    .add_empty();
+            }
+        });
+    };
+
+    harness.run(&mut layout);
+    let header = harness.node_id("header");
+    egui_taffy::TaffyState::with(harness.ctx(), harness.id(), |state| {
+        state.set_style_override(
+            header,
+            sticky_header(taffy::Rect {
+                top: length(10.),
+                ..taffy::Rect::auto()
+            }),
+        );
+    });
+    harness.run(&mut layout);
+    let result = harness.run(&mut layout);
+
+    let list = result.rect("list").unwrap();
+    assert_eq!(result.rect("header").unwrap().top(), list.top() + 10.);
+}
+
+const HEADER_COLOR: egui::Color32 = egui::Color32::from_rgb(1, 2, 3);
+
+/// Header painted in `HEADER_COLOR` is painted in the ui layer
+fn header_painted_in_layer(ui: &egui::Ui) -> bool {
+    ui.ctx().graphics(|graphics| {
+        graphics.get(ui.layer_id()).is_some_and(|paint_list| {
+            paint_list.all_entries().any(|shape| {
+                matches!(&shape.shape, egui::Shape::Rect(rect) if rect.fill == HEADER_COLOR)
+            })
+        })
+    })
+}
+
+/// Scrolled list with a stuck header painted in `HEADER_COLOR`, list is placed in a memoized node.
+/// Memo key changes in the first frames, so that the list is scrolled before it is recorded.
+fn memoized_sticky_list<'a>(
+    runs: &'a Cell<usize>,
+    header_stuck: &'a Cell<bool>,
+    header_painted: &'a Cell<bool>,
+) -> impl FnMut(&mut egui_taffy::Tui) + 'a {
+    let mut frame = 0;
+    move |tui| {
+        frame += 1;
+        tui.id("memo").add_memo(frame.min(4), |tui| {
+            runs.set(runs.get() + 1);
+            if frame == 1 {
+                tui.set_scroll_offset("list", egui::vec2(0., 200.));
+            }
+            tui.id("list").style(scroll_list(100.)).add(|tui| {
+                for idx in 0..5 {
+                    tui.id(egui::Id::new(idx))
+                        .style(fixed(200., 30.))
+                        .add_empty();
+                }
+                tui.id("header")
+                    .style(sticky_header(taffy::Rect {
+                        top: length(0.),
+                        ..taffy::Rect::auto()
+                    }))
+                    .position_sticky()
+                    .add(|tui| {
+                        let rect = tui.egui_ui().max_rect();
+                        tui.egui_ui().painter().rect_filled(rect, 0., HEADER_COLOR);
+                    });
+                header_stuck.set(tui.stuck("header").y);
+                for idx in 5..20 {
+                    tui.id(egui::Id::new(idx))
+                        .style(fixed(200., 30.))
+                        .add_empty();
+                }
+            });
+        });
+
+        header_painted.set(header_painted_in_layer(tui.egui_ui()));
+    }
+}
+
+#[test]
+fn stuck_node_of_memoized_node_is_replayed() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let runs = Cell::new(0);
+    let header_stuck = Cell::new(false);
+    let header_painted = Cell::new(false);
+
+    let mut layout = memoized_sticky_list(&runs, &header_stuck, &header_painted);
+    harness.run_frames(5, &mut layout);
+    assert!(header_stuck.get());
+    let runs_before = runs.get();
+
+    // Stuck header is recorded with the memoized content in the Tui layer
+    harness.run_frames(2, &mut layout);
+    assert_eq!(runs.get(), runs_before);
+    assert!(header_painted.get());
+}
+
+#[test]
+fn stuck_node_is_double_buffered() {
+    let ctx = egui::Context::default();
+
+    let mut header_stuck = false;
+    let mut header_painted = false;
+    for frame in 0..4 {
+        common::frame(&ctx, Vec::new(), |ui| {
+            egui_taffy::tui(ui, "double_buffered")
+                .reserve_available_space()
+                .double_buffer(true)
+                .show(|tui| {
+                    if frame == 0 {
+                        tui.set_scroll_offset("list", egui::vec2(0., 200.));
+                    }
+                    tui.id("list").style(scroll_list(100.)).add(|tui| {
+                        tui.id("header")
+                            .style(sticky_header(taffy::Rect {
+                                top: length(0.),
+                                ..taffy::Rect::auto()
+                            }))
+                            .position_sticky()
+                            .add(|tui| {
+                                let rect = tui.egui_ui().max_rect();
+                                tui.egui_ui().painter().rect_filled(rect, 0., HEADER_COLOR);
+                            });
+                        for row in 0..20 {
+                            tui.id(egui::Id::new(row))
+                                .style(fixed(200., 30.))
+                                .add_empty();
+                        }
+                        header_stuck = tui.stuck("header").y;
+                    });
+                });
+            header_painted = header_painted_in_layer(ui);
+        });
+    }
+
+    // Settled paint of the Tui layer includes the stuck header
+    assert!(header_stuck);
+    assert!(header_painted);
+}
@@ -0,0 +1,146 @@
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TuiBuilderLogic, Visibility, testing::TuiTestHarness};
+
+const LONG_TEXT: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod \
+    tempor incididunt ut labore et dolore magna aliqua.";
+
+fn column(width: f32) -> taffy::Style {
+    taffy::Style {
+        flex_direction: taffy::FlexDirection::Column,
+        align_items: Some(taffy::AlignItems::Start),
+        size: taffy::Size {
+            width: length(width),
+            height: auto(),
+        },
+        ..Default::default()
+    }
+}
+
+fn wrapping(tui: &mut egui_taffy::Tui) {
+    tui.egui_style_mut().wrap_mode = Some(egui::TextWrapMode::Wrap);
+}
+
+#[test]
+fn min_content_width_is_widest_word() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let result = harness.run(|tui| {
+        wrapping(tui);
+        tui.id("narrow").style(column(1.)).add(|tui| {
+            tui.id("words").label("Supercalifragilistic word");
+        });
+        tui.id("wide").style(column(400.)).add(|tui| {
+            tui.id("word").label("Supercalifragilistic");
+            tui.id("line").label("word");
+        });
+    });
+
+    let words = result.rect("words").unwrap();
+    let word = result.rect("word").unwrap();
+    let line = result.rect("line").unwrap();
+
+    // Word is not broken, the second word is wrapped to the next row
+    assert_eq!(words.width(), word.width());
+    assert_eq!(words.height(), word.height() + line.height());
+}
+
+#[test]
+fn egui_label_is_measured_without_painting() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let result = harness.run(|tui| {
+        wrapping(tui);
+        tui.id("shown").style(column(120.)).add(|tui| {
+            tui.id("label").ui_add(egui::Label::new(LONG_TEXT));
+        });
+        tui.id("hidden")
+            .style(column(120.))
+            .visibility(Visibility::Hidden)
+            .add(|tui| {
+                tui.id("label").ui_add(egui::Label::new(LONG_TEXT));
+            });
+    });
+
+    let rects = [result.rect("shown"), result.rect("hidden")].map(Option::unwrap);
+    // Text is wrapped to multiple rows in both nodes
+    assert!(rects[0].height() > 3. * harness.ctx().style().spacing.interact_size.y);
+    assert_eq!(rects[0].size(), rects[1].size());
+}
+
+#[test]
+fn egui_label_options_are_preserved() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let mut responses = Vec::new();
+    let mut text_colors = Vec::new();
+    let mut layout = |tui: &mut egui_taffy::Tui| {
+        responses = vec![
+            tui.id("plain")
+                .ui_add(egui::Label::new("Plain").selectable(false)),
+            tui.id("clickable")
+                .ui_add(egui::Label::new("Clickable").sense(egui::Sense::click())),
+        ];
+        let ui = tui.egui_ui();
+        text_colors = ui.ctx().graphics(|graphics| {
+            graphics
+                .get(ui.layer_id())
+                .map(|paint_list| {
+                    paint_list
+                        .all_entries()
+                        .filter_map(|shape| match &shape.shape {
+                            egui::Shape::Text(text) => Some(text.fallback_color),
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+    };
+
+    harness.run(&mut layout);
+    harness.hover("clickable");
+    harness.run(&mut layout);
+
+    // Label is not selectable even though labels are selectable by default
+    assert!(harness.ctx().style().interaction.selectable_labels);
+    assert!(!responses[0].sense.senses_click());
+    // Label with click sense is painted with interactive color
+    assert!(responses[1].hovered());
+    let visuals = &harness.ctx().style().visuals;
+    assert_eq!(
+        text_colors,
+        [visuals.text_color(), visuals.widgets.hovered.text_color()]
+    );
+}
+
+#[test]
+fn egui_label_outside_of_clip_rect_is_measured() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let result = harness.run(|tui| {
+        wrapping(tui);
+        tui.id("list")
+            .style(taffy::Style {
+                overflow: taffy::Point {
+                    x: taffy::Overflow::Visible,
+                    y: taffy::Overflow::Scroll,
+                },
+                size: taffy::Size {
+                    width: length(120.),
+                    height: length(100.),
+                },
+                ..column(120.)
+            })
+            .add(|tui| {
+                for row in 0..5 {
+                    tui.id(egui::Id::new(row))
+                        .ui_add(egui::Label::new(LONG_TEXT));
+                }
+            });
+    });
+
+    let first = result.rect(egui::Id::new(0)).unwrap();
+    let last = result.rect(egui::Id::new(4)).unwrap();
+    assert!(!result.rect("list").unwrap().intersects(last));
+    assert_eq!(first.size(), last.size());
+}
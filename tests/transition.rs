@@ -0,0 +1,78 @@
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::transition::NodeTransition;
+use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+
+const ROW_WIDTH: f32 = 100.;
+
+/// Column of rows with exit transition, returns top positions of painted row shapes
+/// in their paint order
+fn show_rows(tui: &mut egui_taffy::Tui, rows: &[&str]) -> Vec<f32> {
+    tui.id("list")
+        .style(taffy::Style {
+            flex_direction: taffy::FlexDirection::Column,
+            ..Default::default()
+        })
+        .add(|tui| {
+            for row in rows {
+                tui.id(*row)
+                    .style(taffy::Style {
+                        size: taffy::Size {
+                            width: length(ROW_WIDTH),
+                            height: length(20.),
+                        },
+                        flex_shrink: 0.,
+                        ..Default::default()
+                    })
+                    .transition(NodeTransition::new(1.))
+                    .add(|tui| {
+                        let rect = tui.egui_ui().max_rect();
+                        tui.egui_ui()
+                            .painter()
+                            .rect_filled(rect, 0., egui::Color32::RED);
+                    });
+            }
+        });
+
+    let ui = tui.egui_ui();
+    ui.ctx().graphics(|graphics| {
+        graphics
+            .get(ui.layer_id())
+            .map(|paint_list| {
+                paint_list
+                    .all_entries()
+                    .map(|shape| shape.shape.visual_bounding_rect())
+                    .filter(|rect| rect.width() == ROW_WIDTH)
+                    .map(|rect| rect.top())
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+#[test]
+fn removed_node_is_kept_during_exit_transition() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let shown = harness.run_frames(90, |tui| {
+        show_rows(tui, &["a", "b", "c"]);
+    });
+    // Enter transition is finished
+    let b = shown.rect("b").unwrap();
+
+    let mut painted = Vec::new();
+    let exiting = harness.run_frames(2, |tui| {
+        painted = show_rows(tui, &["a", "c"]);
+    });
+    // Node keeps its layout slot while it fades out
+    assert_eq!(exiting.rect("b"), Some(b));
+    assert_eq!(exiting.rect("c"), shown.rect("c"));
+    // Exiting node is painted between its siblings
+    assert_eq!(painted.len(), 3);
+    assert!(painted.is_sorted(), "{painted:?}");
+
+    let removed = harness.run_frames(90, |tui| {
+        show_rows(tui, &["a", "c"]);
+    });
+    assert!(removed.rect("b").is_none());
+    assert_eq!(removed.rect("c").unwrap().top(), b.top());
+}
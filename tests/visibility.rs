@@ -0,0 +1,106 @@
+use std::cell::Cell;
+
+use egui_taffy::taffy::{self, prelude::*};
+use egui_taffy::{TuiBuilderLogic, testing::TuiTestHarness};
+
+fn panel_style(display: taffy::Display) -> taffy::Style {
+    taffy::Style {
+        display,
+        size: taffy::Size {
+            width: length(200.),
+            height: length(100.),
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn display_none_skips_content_and_keeps_children() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let content_runs = Cell::new(0);
+    let returned = Cell::new(None);
+    let layout = |display: taffy::Display| {
+        let content_runs = &content_runs;
+        let returned = &returned;
+        move |tui: &mut egui_taffy::Tui| {
+            let value = tui.id("panel").style(panel_style(display)).add(|tui| {
+                content_runs.set(content_runs.get() + 1);
+                tui.id("child").add_empty();
+            });
+            returned.set(value);
+        }
+    };
+
+    let result = harness.run(layout(taffy::Display::Flex));
+    assert!(result.converged());
+    assert!(result.rect("child").is_some());
+    assert_eq!(returned.get(), Some(()));
+
+    content_runs.set(0);
+    let result = harness.run(layout(taffy::Display::None));
+
+    assert_eq!(
+        content_runs.get(),
+        0,
+        "content of display none node was executed"
+    );
+    assert_eq!(returned.get(), None);
+    assert_eq!(result.rect("panel").unwrap().size(), egui::Vec2::ZERO);
+    // Child node is kept in the taffy tree while its parent is hidden
+    assert!(result.rect("child").is_some());
+}
+
+#[test]
+fn display_none_keeps_child_state() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+    let child_node = |harness: &TuiTestHarness| {
+        let child = harness.node_id("child");
+        egui_taffy::TaffyState::with(harness.ctx(), harness.id(), |state| {
+            state.items().get(&child).map(|data| data.node_id)
+        })
+        .flatten()
+    };
+
+    let layout = |display: taffy::Display| {
+        move |tui: &mut egui_taffy::Tui| {
+            tui.id("panel").style(panel_style(display)).add(|tui| {
+                tui.id("child").add_empty();
+            });
+        }
+    };
+
+    harness.run(layout(taffy::Display::Flex));
+    let shown = child_node(&harness).unwrap();
+
+    // Hidden subtree is not collected after many frames
+    harness.run_frames(5, layout(taffy::Display::None));
+    assert_eq!(child_node(&harness), Some(shown));
+
+    harness.run(layout(taffy::Display::Flex));
+    assert_eq!(child_node(&harness), Some(shown));
+}
+
+#[test]
+fn visibility_hidden_keeps_layout_slot() {
+    let mut harness = TuiTestHarness::new(egui::vec2(800., 600.));
+
+    let mut content_runs = 0;
+    let result = harness.run(|tui| {
+        tui.id("panel")
+            .style(taffy::Style {
+                flex_direction: taffy::FlexDirection::Column,
+                ..Default::default()
+            })
+            .visibility(egui_taffy::Visibility::Hidden)
+            .add(|tui| {
+                content_runs += 1;
+                tui.id("child")
+                    .style(panel_style(taffy::Display::Flex))
+                    .add_empty();
+            });
+    });
+
+    assert!(content_runs > 0);
+    assert_eq!(result.rect("panel").unwrap().size(), egui::vec2(200., 100.));
+}